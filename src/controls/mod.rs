@@ -1,6 +1,19 @@
 //! # Example
 //! ```rust
-//!
+//! # use bevy::prelude::*;
+//! # use leafwing_input_manager::prelude::*;
+//! # use ns_core::controls::{ActionBusyworkTrait, ClientController, InputDefaultsTrait};
+//! # #[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
+//! # pub enum CoreActions {
+//! #     ToggleDevMode,
+//! # }
+//! # impl InputDefaultsTrait for CoreActions {
+//! #     type Action = CoreActions;
+//! #     fn default_input_codes() -> InputMap<Self::Action> {
+//! #         InputMap::default()
+//! #     }
+//! # }
+//! # let mut app = App::new();
 //! // In the app build system
 //!     app.setup_action::<CoreActions>(true);
 //!
@@ -18,6 +31,9 @@
 //!
 //! # Example of setting up your own actions for use
 //! ```rust
+//! # use bevy::prelude::*;
+//! # use leafwing_input_manager::prelude::*;
+//! # use ns_core::controls::InputDefaultsTrait;
 //! #[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
 //! pub enum CoreActions {
 //!    ToggleDevMode,
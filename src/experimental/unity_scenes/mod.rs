@@ -1,18 +1,168 @@
 //! An experimental state management system that is intended to automatically handle meta states.
+//!
+//! Every entity spawned while the given state is active is automatically tagged with [`ManagedByScene`] and despawned when the
+//! state is left, similar to how scenes work in Unity. Entities that should survive leaving the state can opt out with
+//! [`PersistentEntity`].
+//!
+//! Only one state type should be driven by this plugin at a time, otherwise each plugin will claim the entities spawned while
+//! the other's state is active.
+//!
+//! Systems registered in the [`OneShotSystemIds`](crate::one_shot_system::OneShotSystemIds) and pending
+//! [`ScheduledOneShotSystem`] runs are never managed. Systems registered directly with [`World::register_system`] while the
+//! state is active are, so insert [`PersistentEntity`] on their [`SystemId::entity`](bevy::ecs::system::SystemId::entity)
+//! to keep them.
 
-use bevy::{app::Plugin, ecs::component::Component, prelude::States};
+use std::marker::PhantomData;
 
+use bevy::{
+    app::{Last, Plugin},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        observer::ObserverState,
+        query::{QueryState, With, Without},
+        schedule::IntoSystemConfigs,
+        system::RunSystemOnce,
+        world::World,
+    },
+    hierarchy::{DespawnRecursiveExt, Parent},
+    prelude::{
+        in_state, OnExit, StateTransition, StateTransitionEvent, States, TransitionSchedules,
+    },
+    state::state::setup_state_transitions_in_world,
+};
+
+use crate::one_shot_system::{RegisteredOneShotSystem, ScheduledOneShotSystem};
+
+/// Automatically manages every entity spawned while [`UnityScenePlugin::state`] is active
 pub struct UnityScenePlugin<State: States> {
-    state: State,
+    pub state: State,
 }
 
 impl<State: States> Plugin for UnityScenePlugin<State> {
     fn build(&self, app: &mut bevy::prelude::App) {
-        //app.add_systems(OnExit(State::), systems);
+        setup_state_transitions_in_world(app.world_mut());
+
+        // The baseline has to be recorded before any OnEnter systems get the chance to spawn entities for the scene
+        let state = self.state.clone();
+        app.add_systems(
+            StateTransition,
+            record_baseline::<State>
+                .run_if(
+                    move |transitions: EventReader<StateTransitionEvent<State>>| {
+                        entered_state(transitions, &state)
+                    },
+                )
+                .in_set(TransitionSchedules::<State>::default()),
+        );
+        app.add_systems(
+            Last,
+            tag_scene_entities::<State>.run_if(in_state(self.state.clone())),
+        );
+        app.add_systems(OnExit(self.state.clone()), remove_scene_entities::<State>);
     }
 }
 
+/// Marks an entity as belonging to the currently active scene. It will be despawned when the scene is left.
 #[derive(Component)]
 pub struct ManagedByScene;
 
-fn remove_scene_entities() {}
+/// Opts an entity and all of its descendants out of being managed by the scene they were spawned in, so they survive leaving
+/// the scene.
+///
+/// Children of a [`ManagedByScene`] entity are still despawned with their parent.
+#[derive(Component)]
+pub struct PersistentEntity;
+
+/// Marks an entity that the scene has already decided not to manage, either because it existed before the scene was entered
+/// or because it descends from a [`PersistentEntity`]. Keeps the entities left to check every frame down to the newly
+/// spawned ones.
+#[derive(Component)]
+struct Unmanaged<State: States> {
+    pd: PhantomData<State>,
+}
+
+impl<State: States> Default for Unmanaged<State> {
+    fn default() -> Self {
+        Self { pd: PhantomData }
+    }
+}
+
+fn entered_state<State: States>(
+    mut transitions: EventReader<StateTransitionEvent<State>>,
+    state: &State,
+) -> bool {
+    transitions.read().last().is_some_and(|transition| {
+        transition.entered.as_ref() == Some(state) && transition.exited.as_ref() != Some(state)
+    })
+}
+
+fn record_baseline<State: States>(
+    world: &mut World,
+    query: &mut QueryState<Entity, Without<Unmanaged<State>>>,
+) {
+    let entities: Vec<Entity> = query.iter(world).collect();
+    for entity in entities {
+        world
+            .entity_mut(entity)
+            .insert(Unmanaged::<State>::default());
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn tag_scene_entities<State: States>(
+    world: &mut World,
+    query: &mut QueryState<
+        Entity,
+        (
+            Without<ManagedByScene>,
+            Without<Unmanaged<State>>,
+            Without<PersistentEntity>,
+            Without<ObserverState>,
+            Without<RegisteredOneShotSystem>,
+            Without<ScheduledOneShotSystem>,
+        ),
+    >,
+) {
+    let untagged: Vec<Entity> = query.iter(world).collect();
+    for entity in untagged {
+        if has_persistent_ancestor(world, entity) {
+            world
+                .entity_mut(entity)
+                .insert(Unmanaged::<State>::default());
+        } else {
+            world.entity_mut(entity).insert(ManagedByScene);
+        }
+    }
+}
+
+fn has_persistent_ancestor(world: &World, entity: Entity) -> bool {
+    let mut current = entity;
+    while let Some(parent) = world.get::<Parent>(current) {
+        current = parent.get();
+        if world.get::<PersistentEntity>(current).is_some() {
+            return true;
+        }
+    }
+    false
+}
+
+fn remove_scene_entities<State: States>(
+    world: &mut World,
+    query: &mut QueryState<Entity, With<ManagedByScene>>,
+) {
+    // Catch anything spawned since the last time entities were tagged
+    world.run_system_once(tag_scene_entities::<State>);
+
+    // Entities may have been parented to a persistent entity after they were tagged
+    let entities: Vec<Entity> = query
+        .iter(world)
+        .filter(|entity| !has_persistent_ancestor(world, *entity))
+        .collect();
+    for entity in entities {
+        if let Some(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+}
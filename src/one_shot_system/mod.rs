@@ -0,0 +1,695 @@
+use std::{
+    any::{type_name, Any},
+    fmt::Display,
+    marker::PhantomData,
+};
+
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::Event,
+        observer::{Observer, Trigger},
+        system::{Commands, IntoSystem, Resource, SystemId},
+        world::{OnAdd, OnRemove, World},
+    },
+    log::warn,
+    reflect::TypePath,
+    utils::HashMap,
+};
+
+use self::scheduled::{cancel_scheduled_system, run_scheduled_systems, schedule_system};
+
+pub use self::scheduled::{OneShotSchedule, ScheduledOneShotSystem, ScheduledSystemHandle};
+
+mod scheduled;
+
+/// Plugin for the one shot system extensions
+pub struct OneShotSystemPlugin;
+
+impl Plugin for OneShotSystemPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<OneShotSystemIds>();
+        app.add_event::<OneShotSystemError>();
+        app.add_systems(Update, run_scheduled_systems);
+    }
+}
+
+/// The label of systems registered without one
+pub const DEFAULT_LABEL: &str = "default";
+/// The label of systems registered to run when their marker component is added to an entity
+pub const ON_ADD_LABEL: &str = "on_add";
+/// The label of systems registered to run when their marker component is removed from an entity
+pub const ON_REMOVE_LABEL: &str = "on_remove";
+
+/// When a triggered one shot system runs, see [`OneShotSystemAppExtension::register_triggered_one_shot_system`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarkerTrigger {
+    /// Runs whenever the marker component is added to an entity
+    Added,
+    /// Runs whenever the marker component is removed from an entity, including when the entity is despawned
+    Removed,
+}
+
+/// Identifies a registered one shot system by the [`TypePath`] of its marker component and its label
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OneShotSystemKey {
+    pub marker: &'static str,
+    pub label: &'static str,
+}
+
+/// A description of a registered one shot system, see [`OneShotSystemIds::registered`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OneShotSystemInfo {
+    pub key: OneShotSystemKey,
+    /// The type name of the systems input
+    pub input: &'static str,
+    /// The type name of the systems output
+    pub output: &'static str,
+}
+
+/// Every registered one shot system. A marker component can have any number of systems as long as each has its own label.
+#[derive(Resource, Default)]
+pub struct OneShotSystemIds {
+    entries: HashMap<OneShotSystemKey, RegisteredSystem>,
+    /// The observers running triggered systems, keyed by their marker and [`ON_ADD_LABEL`] or [`ON_REMOVE_LABEL`]
+    observers: HashMap<OneShotSystemKey, Entity>,
+}
+
+/// Marks the entity that stores a system registered in the [`OneShotSystemIds`], so that it can be filtered out of queries
+/// over every entity
+#[derive(Component)]
+pub struct RegisteredOneShotSystem;
+
+struct RegisteredSystem {
+    /// A boxed `SystemId<I, O>`
+    system_id: Box<dyn Any + Send + Sync>,
+    input: &'static str,
+    output: &'static str,
+    /// Removes the system from the world, knowing its input and output types
+    remove: fn(&mut World, &(dyn Any + Send + Sync)),
+}
+
+fn remove_registered_system<I: 'static, O: 'static>(
+    world: &mut World,
+    system_id: &(dyn Any + Send + Sync),
+) {
+    if let Some(system_id) = system_id.downcast_ref::<SystemId<I, O>>() {
+        if let Err(error) = world.remove_system(*system_id) {
+            warn!("Failed to remove one shot system: {error}");
+        }
+    }
+}
+
+impl OneShotSystemIds {
+    /// Gets the system registered for the given marker components [`TypePath`] and label
+    pub fn get(
+        &self,
+        marker: &'static str,
+        label: &'static str,
+    ) -> Result<SystemId, OneShotSystemError> {
+        self.get_typed(marker, label)
+    }
+
+    /// Gets the typed system registered for the given marker components [`TypePath`] and label
+    pub fn get_typed<I: 'static, O: 'static>(
+        &self,
+        marker: &'static str,
+        label: &'static str,
+    ) -> Result<SystemId<I, O>, OneShotSystemError> {
+        self.entries
+            .get(&OneShotSystemKey { marker, label })
+            .ok_or(OneShotSystemError::NotRegistered { marker, label })?
+            .system_id
+            .downcast_ref::<SystemId<I, O>>()
+            .copied()
+            .ok_or(OneShotSystemError::MismatchedTypes {
+                marker,
+                label,
+                input: type_name::<I>(),
+                output: type_name::<O>(),
+            })
+    }
+
+    /// Returns true if a system is registered for the given marker components [`TypePath`] and label
+    pub fn contains(&self, marker: &'static str, label: &'static str) -> bool {
+        self.entries
+            .contains_key(&OneShotSystemKey { marker, label })
+    }
+
+    /// Inserts the system, returning an error if the marker already had a system with the same label. The new system replaces
+    /// the old one either way, but the old one isn't removed from the world. Use
+    /// [`OneShotSystemWorldExt::replace_marker_system`] to do both.
+    pub fn insert<I: 'static, O: 'static>(
+        &mut self,
+        marker: &'static str,
+        label: &'static str,
+        system_id: SystemId<I, O>,
+    ) -> Result<(), OneShotSystemError> {
+        match self.insert_entry(marker, label, system_id) {
+            Some(_) => Err(OneShotSystemError::DuplicateRegistration { marker, label }),
+            None => Ok(()),
+        }
+    }
+
+    fn insert_entry<I: 'static, O: 'static>(
+        &mut self,
+        marker: &'static str,
+        label: &'static str,
+        system_id: SystemId<I, O>,
+    ) -> Option<RegisteredSystem> {
+        let registered = RegisteredSystem {
+            system_id: Box::new(system_id),
+            input: type_name::<I>(),
+            output: type_name::<O>(),
+            remove: remove_registered_system::<I, O>,
+        };
+        self.entries
+            .insert(OneShotSystemKey { marker, label }, registered)
+    }
+
+    /// Every registered system
+    pub fn registered(&self) -> impl Iterator<Item = OneShotSystemInfo> + '_ {
+        self.entries
+            .iter()
+            .map(|(key, registered)| OneShotSystemInfo {
+                key: *key,
+                input: registered.input,
+                output: registered.output,
+            })
+    }
+
+    /// The labels of every system registered for the given marker components [`TypePath`]
+    pub fn labels(&self, marker: &'static str) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries
+            .keys()
+            .filter(move |key| key.marker == marker)
+            .map(|key| key.label)
+    }
+}
+
+/// Errors from registering or looking up one shot systems.
+///
+/// These are logged as warnings and sent as events instead of panicking so that a missing or duplicated registration doesn't
+/// take down the app.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OneShotSystemError {
+    /// A system was registered for a marker component that already had one with the same label. The new system replaced the
+    /// old one.
+    DuplicateRegistration {
+        marker: &'static str,
+        label: &'static str,
+    },
+    /// A system was requested for a marker component that never had one registered
+    NotRegistered {
+        marker: &'static str,
+        label: &'static str,
+    },
+    /// A typed system was requested with an input or output type that doesn't match the one it was registered with
+    MismatchedTypes {
+        marker: &'static str,
+        label: &'static str,
+        input: &'static str,
+        output: &'static str,
+    },
+    /// The system was found but couldn't be run, either because it was removed or because it tried to run itself
+    RunFailed {
+        marker: &'static str,
+        label: &'static str,
+    },
+}
+
+impl Display for OneShotSystemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OneShotSystemError::DuplicateRegistration { marker, label } => write!(
+                f,
+                "A one shot system labeled {label} was already registered for {marker}, it has been replaced"
+            ),
+            OneShotSystemError::NotRegistered { marker, label } => write!(
+                f,
+                "No one shot system labeled {label} is registered for {marker}, did you forget to register it?"
+            ),
+            OneShotSystemError::MismatchedTypes {
+                marker,
+                label,
+                input,
+                output,
+            } => write!(
+                f,
+                "The one shot system labeled {label} registered for {marker} doesn't take {input} and return {output}"
+            ),
+            OneShotSystemError::RunFailed { marker, label } => write!(
+                f,
+                "The one shot system labeled {label} registered for {marker} failed to run"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OneShotSystemError {}
+
+/// Inserts the system id into the [`OneShotSystemIds`] registry, initializing it if it doesn't exist yet and reporting a
+/// [`OneShotSystemError::DuplicateRegistration`] if the marker already had a system with the same label.
+pub(crate) fn insert_system_id<I: 'static, O: 'static>(
+    app: &mut App,
+    marker: &'static str,
+    label: &'static str,
+    system_id: SystemId<I, O>,
+) {
+    app.add_event::<OneShotSystemError>();
+    let world = app.world_mut();
+    if replace_system_id(world, marker, label, system_id) {
+        report_error(
+            world,
+            OneShotSystemError::DuplicateRegistration { marker, label },
+        );
+    }
+}
+
+/// Inserts the system id into the [`OneShotSystemIds`] registry, removing any system it replaced from the world. Returns true if
+/// a system was replaced.
+pub(crate) fn replace_system_id<I: 'static, O: 'static>(
+    world: &mut World,
+    marker: &'static str,
+    label: &'static str,
+    system_id: SystemId<I, O>,
+) -> bool {
+    if let Some(mut entity) = world.get_entity_mut(system_id.entity()) {
+        entity.insert(RegisteredOneShotSystem);
+    }
+    let replaced = world
+        .get_resource_or_insert_with(OneShotSystemIds::default)
+        .insert_entry(marker, label, system_id);
+    let Some(replaced) = replaced else {
+        return false;
+    };
+    (replaced.remove)(world, replaced.system_id.as_ref());
+    true
+}
+
+/// Removes the system from the [`OneShotSystemIds`] registry and from the world
+pub(crate) fn unregister_system(
+    world: &mut World,
+    marker: &'static str,
+    label: &'static str,
+) -> Result<(), OneShotSystemError> {
+    let removed = world
+        .get_resource_mut::<OneShotSystemIds>()
+        .and_then(|mut ids| ids.entries.remove(&OneShotSystemKey { marker, label }))
+        .ok_or(OneShotSystemError::NotRegistered { marker, label })?;
+    (removed.remove)(world, removed.system_id.as_ref());
+
+    // Triggered systems also have an observer that would otherwise keep reporting the system as missing
+    let observer = world
+        .resource_mut::<OneShotSystemIds>()
+        .observers
+        .remove(&OneShotSystemKey { marker, label });
+    if let Some(observer) = observer.and_then(|observer| world.get_entity_mut(observer)) {
+        observer.despawn();
+    }
+    Ok(())
+}
+
+/// Logs the given error as a warning and sends it as a [`OneShotSystemError`] event
+pub(crate) fn report_error(world: &mut World, error: OneShotSystemError) {
+    warn!("{error}");
+    world.send_event(error);
+}
+
+pub trait OneShotSystemAppExtension {
+    /// Registers the given system using the given marker component [`TypePath`].
+    fn register_one_shot_system<Marker: Component + TypePath, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+
+        system: impl IntoSystem<(), (), M> + 'static,
+    );
+
+    /// Registers the given system that takes an [`In<I>`](bevy::ecs::system::In) and returns an `O` using the given marker
+    /// component [`TypePath`]. Run it with [`OneShotSystemWorldExt::run_marker_system_with`] or
+    /// [`OneShotSystemCommandsExt::run_marker_system_with`].
+    fn register_one_shot_system_with_io<Marker: Component + TypePath, I: 'static, O: 'static, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        system: impl IntoSystem<I, O, M> + 'static,
+    );
+
+    /// Registers the given system under a label so that a marker component can have several systems, such as `open`,
+    /// `refresh`, and `close`. Registering the same label twice for a marker reports a
+    /// [`OneShotSystemError::DuplicateRegistration`].
+    fn register_labeled_one_shot_system<Marker: Component + TypePath, I: 'static, O: 'static, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        label: &'static str,
+        system: impl IntoSystem<I, O, M> + 'static,
+    );
+
+    /// Registers the given system to run automatically whenever the marker component is added to, or removed from, an entity.
+    /// The system is handed the entity as [`In<Entity>`](bevy::ecs::system::In).
+    ///
+    /// The system runs as a command after the change, so a system reacting to [`MarkerTrigger::Removed`] may find the entity
+    /// already despawned. It is registered under [`ON_ADD_LABEL`] or [`ON_REMOVE_LABEL`] so it can also be run by hand.
+    fn register_triggered_one_shot_system<Marker: Component + TypePath, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        trigger: MarkerTrigger,
+        system: impl IntoSystem<Entity, (), M> + 'static,
+    );
+}
+
+impl OneShotSystemAppExtension for App {
+    fn register_one_shot_system<Marker: Component + TypePath, M>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+
+        system: impl IntoSystem<(), (), M> + 'static,
+    ) {
+        self.register_labeled_one_shot_system(marker_component, DEFAULT_LABEL, system);
+    }
+
+    fn register_one_shot_system_with_io<Marker: Component + TypePath, I: 'static, O: 'static, M>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        system: impl IntoSystem<I, O, M> + 'static,
+    ) {
+        self.register_labeled_one_shot_system(marker_component, DEFAULT_LABEL, system);
+    }
+
+    fn register_labeled_one_shot_system<Marker: Component + TypePath, I: 'static, O: 'static, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        label: &'static str,
+        system: impl IntoSystem<I, O, M> + 'static,
+    ) {
+        let system_id = self.world_mut().register_system(system);
+        insert_system_id(self, Marker::type_path(), label, system_id);
+    }
+
+    fn register_triggered_one_shot_system<Marker: Component + TypePath, M>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        trigger: MarkerTrigger,
+        system: impl IntoSystem<Entity, (), M> + 'static,
+    ) {
+        let label = match trigger {
+            MarkerTrigger::Added => ON_ADD_LABEL,
+            MarkerTrigger::Removed => ON_REMOVE_LABEL,
+        };
+        self.register_labeled_one_shot_system(marker_component, label, system);
+
+        // The observer looks the system up when it runs, so registering again only replaces the system
+        let key = OneShotSystemKey {
+            marker: Marker::type_path(),
+            label,
+        };
+        let world = self.world_mut();
+        if world
+            .resource::<OneShotSystemIds>()
+            .observers
+            .contains_key(&key)
+        {
+            return;
+        }
+        let observer = match trigger {
+            MarkerTrigger::Added => world.spawn(Observer::new(
+                |trigger: Trigger<OnAdd, Marker>, mut commands: Commands| {
+                    commands.run_labeled_marker_system_with::<Marker, Entity, ()>(
+                        ON_ADD_LABEL,
+                        trigger.entity(),
+                    );
+                },
+            )),
+            MarkerTrigger::Removed => world.spawn(Observer::new(
+                |trigger: Trigger<OnRemove, Marker>, mut commands: Commands| {
+                    commands.run_labeled_marker_system_with::<Marker, Entity, ()>(
+                        ON_REMOVE_LABEL,
+                        trigger.entity(),
+                    );
+                },
+            )),
+        }
+        .id();
+        world
+            .resource_mut::<OneShotSystemIds>()
+            .observers
+            .insert(key, observer);
+    }
+}
+
+pub trait OneShotSystemWorldExt {
+    /// Runs the system registered for the marker component with [`OneShotSystemAppExtension::register_one_shot_system`].
+    ///
+    /// Errors are also logged and sent as [`OneShotSystemError`] events.
+    fn run_marker_system<Marker: Component + TypePath>(&mut self)
+        -> Result<(), OneShotSystemError>;
+
+    /// Runs the system registered for the marker component under the given label.
+    ///
+    /// Errors are also logged and sent as [`OneShotSystemError`] events.
+    fn run_labeled_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        label: &'static str,
+    ) -> Result<(), OneShotSystemError>;
+
+    /// Runs the typed system registered for the marker component with the given input and returns its output.
+    ///
+    /// Errors are also logged and sent as [`OneShotSystemError`] events.
+    fn run_marker_system_with<Marker: Component + TypePath, I: 'static, O: 'static>(
+        &mut self,
+        input: I,
+    ) -> Result<O, OneShotSystemError>;
+
+    /// Runs the typed system registered for the marker component under the given label and returns its output.
+    ///
+    /// Errors are also logged and sent as [`OneShotSystemError`] events.
+    fn run_labeled_marker_system_with<Marker: Component + TypePath, I: 'static, O: 'static>(
+        &mut self,
+        label: &'static str,
+        input: I,
+    ) -> Result<O, OneShotSystemError>;
+
+    /// Schedules the system registered for the marker component under the given label to run later, or repeatedly.
+    ///
+    /// Scheduled systems are run by the [`OneShotSystemPlugin`].
+    fn schedule_labeled_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        label: &'static str,
+        schedule: OneShotSchedule,
+    ) -> ScheduledSystemHandle;
+
+    /// Cancels a scheduled system. Does nothing if it already ran or was already cancelled.
+    fn cancel_scheduled_system(&mut self, handle: ScheduledSystemHandle);
+
+    /// Removes the system registered for the marker component under the given label from the registry and from the world
+    fn unregister_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        label: &'static str,
+    ) -> Result<(), OneShotSystemError>;
+
+    /// Registers the given system for the marker component under the given label, replacing and removing any system that
+    /// was already registered there. Unlike registering twice this isn't reported as an error.
+    fn replace_marker_system<Marker: Component + TypePath, I: 'static, O: 'static, M>(
+        &mut self,
+        label: &'static str,
+        system: impl IntoSystem<I, O, M> + 'static,
+    );
+}
+
+impl OneShotSystemWorldExt for World {
+    fn run_marker_system<Marker: Component + TypePath>(
+        &mut self,
+    ) -> Result<(), OneShotSystemError> {
+        self.run_labeled_marker_system_with::<Marker, (), ()>(DEFAULT_LABEL, ())
+    }
+
+    fn run_labeled_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        label: &'static str,
+    ) -> Result<(), OneShotSystemError> {
+        self.run_labeled_marker_system_with::<Marker, (), ()>(label, ())
+    }
+
+    fn run_marker_system_with<Marker: Component + TypePath, I: 'static, O: 'static>(
+        &mut self,
+        input: I,
+    ) -> Result<O, OneShotSystemError> {
+        self.run_labeled_marker_system_with::<Marker, I, O>(DEFAULT_LABEL, input)
+    }
+
+    fn run_labeled_marker_system_with<Marker: Component + TypePath, I: 'static, O: 'static>(
+        &mut self,
+        label: &'static str,
+        input: I,
+    ) -> Result<O, OneShotSystemError> {
+        let marker = Marker::type_path();
+        let result = self
+            .get_resource::<OneShotSystemIds>()
+            .ok_or(OneShotSystemError::NotRegistered { marker, label })
+            .and_then(|ids| ids.get_typed::<I, O>(marker, label))
+            .and_then(|system_id| {
+                self.run_system_with_input(system_id, input)
+                    .map_err(|_| OneShotSystemError::RunFailed { marker, label })
+            });
+        if let Err(error) = result {
+            report_error(self, error);
+        }
+        result
+    }
+
+    fn schedule_labeled_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        label: &'static str,
+        schedule: OneShotSchedule,
+    ) -> ScheduledSystemHandle {
+        let key = OneShotSystemKey {
+            marker: Marker::type_path(),
+            label,
+        };
+        ScheduledSystemHandle(self.spawn(ScheduledOneShotSystem::new(key, schedule)).id())
+    }
+
+    fn cancel_scheduled_system(&mut self, handle: ScheduledSystemHandle) {
+        cancel_scheduled_system(self, handle);
+    }
+
+    fn unregister_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        label: &'static str,
+    ) -> Result<(), OneShotSystemError> {
+        let result = unregister_system(self, Marker::type_path(), label);
+        if let Err(error) = result {
+            report_error(self, error);
+        }
+        result
+    }
+
+    fn replace_marker_system<Marker: Component + TypePath, I: 'static, O: 'static, M>(
+        &mut self,
+        label: &'static str,
+        system: impl IntoSystem<I, O, M> + 'static,
+    ) {
+        let system_id = self.register_system(system);
+        replace_system_id(self, Marker::type_path(), label, system_id);
+    }
+}
+
+pub trait OneShotSystemCommandsExt {
+    /// Queues running the system registered for the marker component with
+    /// [`OneShotSystemAppExtension::register_one_shot_system`]. If nothing is registered a
+    /// [`OneShotSystemError::NotRegistered`] is logged and sent as an event.
+    fn run_marker_system<Marker: Component + TypePath>(&mut self);
+
+    /// Queues running the system registered for the marker component under the given label
+    fn run_labeled_marker_system<Marker: Component + TypePath>(&mut self, label: &'static str);
+
+    /// Schedules the system registered for the marker component with
+    /// [`OneShotSystemAppExtension::register_one_shot_system`] to run later, or repeatedly, returning a handle that can be
+    /// used to cancel it.
+    ///
+    /// Scheduled systems are run by the [`OneShotSystemPlugin`].
+    fn schedule_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        schedule: OneShotSchedule,
+    ) -> ScheduledSystemHandle;
+
+    /// Schedules the system registered for the marker component under the given label to run later, or repeatedly
+    fn schedule_labeled_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        label: &'static str,
+        schedule: OneShotSchedule,
+    ) -> ScheduledSystemHandle;
+
+    /// Cancels a scheduled system. Does nothing if it already ran or was already cancelled.
+    fn cancel_scheduled_system(&mut self, handle: ScheduledSystemHandle);
+
+    /// Queues removing the system registered for the marker component under the given label from the registry and from the
+    /// world
+    fn unregister_marker_system<Marker: Component + TypePath>(&mut self, label: &'static str);
+
+    /// Queues running the typed system registered for the marker component with the given input. The output is discarded,
+    /// use [`OneShotSystemWorldExt::run_marker_system_with`] to get it.
+    fn run_marker_system_with<Marker: Component + TypePath, I: Send + 'static, O: 'static>(
+        &mut self,
+        input: I,
+    );
+
+    /// Queues running the typed system registered for the marker component under the given label with the given input
+    fn run_labeled_marker_system_with<Marker: Component + TypePath, I: Send + 'static, O: 'static>(
+        &mut self,
+        label: &'static str,
+        input: I,
+    );
+}
+
+impl OneShotSystemCommandsExt for Commands<'_, '_> {
+    fn run_marker_system<Marker: Component + TypePath>(&mut self) {
+        self.run_labeled_marker_system_with::<Marker, (), ()>(DEFAULT_LABEL, ());
+    }
+
+    fn run_labeled_marker_system<Marker: Component + TypePath>(&mut self, label: &'static str) {
+        self.run_labeled_marker_system_with::<Marker, (), ()>(label, ());
+    }
+
+    fn schedule_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        schedule: OneShotSchedule,
+    ) -> ScheduledSystemHandle {
+        self.schedule_labeled_marker_system::<Marker>(DEFAULT_LABEL, schedule)
+    }
+
+    fn schedule_labeled_marker_system<Marker: Component + TypePath>(
+        &mut self,
+        label: &'static str,
+        schedule: OneShotSchedule,
+    ) -> ScheduledSystemHandle {
+        let key = OneShotSystemKey {
+            marker: Marker::type_path(),
+            label,
+        };
+        schedule_system(self, key, schedule)
+    }
+
+    fn cancel_scheduled_system(&mut self, handle: ScheduledSystemHandle) {
+        self.add(move |world: &mut World| cancel_scheduled_system(world, handle));
+    }
+
+    fn unregister_marker_system<Marker: Component + TypePath>(&mut self, label: &'static str) {
+        self.add(move |world: &mut World| {
+            let _ = world.unregister_marker_system::<Marker>(label);
+        });
+    }
+
+    fn run_marker_system_with<Marker: Component + TypePath, I: Send + 'static, O: 'static>(
+        &mut self,
+        input: I,
+    ) {
+        self.run_labeled_marker_system_with::<Marker, I, O>(DEFAULT_LABEL, input);
+    }
+
+    fn run_labeled_marker_system_with<
+        Marker: Component + TypePath,
+        I: Send + 'static,
+        O: 'static,
+    >(
+        &mut self,
+        label: &'static str,
+        input: I,
+    ) {
+        self.add(move |world: &mut World| {
+            let _ = world.run_labeled_marker_system_with::<Marker, I, O>(label, input);
+        });
+    }
+}
+
+/// Helper function to make creating [`MarkerComponent`] function type helpers easier
+///
+///
+pub fn marker_component<Marker: Component + TypePath>() -> MarkerComponent<Marker> {
+    MarkerComponent::<Marker> { pd: PhantomData }
+}
+
+pub struct MarkerComponent<C: Component + TypePath> {
+    pd: PhantomData<C>,
+}
@@ -0,0 +1,157 @@
+//! Running registered one shot systems later, or over and over.
+//!
+//! Each scheduled run is an entity with a [`ScheduledOneShotSystem`] component that counts down and then runs the system
+//! through the [`OneShotSystemIds`] registry. The [`ScheduledSystemHandle`] returned when scheduling can be used to cancel it.
+
+use std::time::Duration;
+
+use bevy::{
+    ecs::{component::Component, entity::Entity, system::Commands, world::World},
+    time::{Real, Time, Timer, TimerMode, Virtual},
+};
+
+use super::{report_error, OneShotSystemError, OneShotSystemIds, OneShotSystemKey};
+
+/// When a scheduled one shot system runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OneShotSchedule {
+    /// Runs once on the given number of frames from now, so `AfterFrames(1)` runs on the next update. `AfterFrames(0)` also
+    /// runs on the next update.
+    AfterFrames(u32),
+    /// Runs once after the given amount of [`Virtual`] time has passed. Doesn't count down while virtual time is paused.
+    After(Duration),
+    /// Runs once after the given amount of [`Real`] time has passed
+    AfterReal(Duration),
+    /// Runs every time the given amount of [`Virtual`] time passes until cancelled
+    Every(Duration),
+    /// Runs every time the given amount of [`Real`] time passes until cancelled
+    EveryReal(Duration),
+}
+
+/// A handle to a scheduled one shot system that can be used to cancel it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ScheduledSystemHandle(pub Entity);
+
+impl ScheduledSystemHandle {
+    /// Returns true if the system is still waiting to run, or is repeating
+    pub fn is_scheduled(&self, world: &World) -> bool {
+        world.get::<ScheduledOneShotSystem>(self.0).is_some()
+    }
+}
+
+/// A one shot system that is waiting to run
+#[derive(Component)]
+pub struct ScheduledOneShotSystem {
+    key: OneShotSystemKey,
+    countdown: Countdown,
+}
+
+enum Countdown {
+    Frames(u32),
+    Virtual(Timer),
+    Real(Timer),
+}
+
+impl ScheduledOneShotSystem {
+    pub(crate) fn new(key: OneShotSystemKey, schedule: OneShotSchedule) -> Self {
+        let countdown = match schedule {
+            OneShotSchedule::AfterFrames(frames) => Countdown::Frames(frames),
+            OneShotSchedule::After(duration) => {
+                Countdown::Virtual(Timer::new(duration, TimerMode::Once))
+            }
+            OneShotSchedule::AfterReal(duration) => {
+                Countdown::Real(Timer::new(duration, TimerMode::Once))
+            }
+            OneShotSchedule::Every(duration) => {
+                Countdown::Virtual(Timer::new(duration, TimerMode::Repeating))
+            }
+            OneShotSchedule::EveryReal(duration) => {
+                Countdown::Real(Timer::new(duration, TimerMode::Repeating))
+            }
+        };
+        Self { key, countdown }
+    }
+
+    /// The system that will be run
+    pub fn key(&self) -> OneShotSystemKey {
+        self.key
+    }
+}
+
+/// Schedules the system registered under the key, returning a handle to cancel it
+pub(crate) fn schedule_system(
+    commands: &mut Commands,
+    key: OneShotSystemKey,
+    schedule: OneShotSchedule,
+) -> ScheduledSystemHandle {
+    ScheduledSystemHandle(
+        commands
+            .spawn(ScheduledOneShotSystem::new(key, schedule))
+            .id(),
+    )
+}
+
+/// Cancels the scheduled system. Does nothing if it already ran or was already cancelled.
+pub(crate) fn cancel_scheduled_system(world: &mut World, handle: ScheduledSystemHandle) {
+    if handle.is_scheduled(world) {
+        world.despawn(handle.0);
+    }
+}
+
+/// Counts down every scheduled system and runs the ones that are due
+pub(crate) fn run_scheduled_systems(world: &mut World) {
+    let virtual_delta = world.resource::<Time<Virtual>>().delta();
+    let real_delta = world.resource::<Time<Real>>().delta();
+
+    let mut due = vec![];
+    let mut query = world.query::<(Entity, &mut ScheduledOneShotSystem)>();
+    for (entity, mut scheduled) in query.iter_mut(world) {
+        let (ready, finished) = match &mut scheduled.countdown {
+            Countdown::Frames(frames) => {
+                *frames = frames.saturating_sub(1);
+                (*frames == 0, *frames == 0)
+            }
+            Countdown::Virtual(timer) => {
+                timer.tick(virtual_delta);
+                (
+                    timer.just_finished(),
+                    timer.finished() && timer.mode() == TimerMode::Once,
+                )
+            }
+            Countdown::Real(timer) => {
+                timer.tick(real_delta);
+                (
+                    timer.just_finished(),
+                    timer.finished() && timer.mode() == TimerMode::Once,
+                )
+            }
+        };
+        if ready {
+            due.push((entity, scheduled.key, finished));
+        }
+    }
+
+    for (entity, key, finished) in due {
+        // An earlier system may have cancelled this one
+        if world.get_entity(entity).is_none() {
+            continue;
+        }
+        if finished {
+            world.despawn(entity);
+        }
+        let system_id = world
+            .get_resource_or_insert_with(OneShotSystemIds::default)
+            .get(key.marker, key.label);
+        let result = system_id.and_then(|system_id| {
+            world
+                .run_system(system_id)
+                .map_err(|_| OneShotSystemError::RunFailed {
+                    marker: key.marker,
+                    label: key.label,
+                })
+        });
+        if let Err(error) = result {
+            report_error(world, error);
+        }
+    }
+}
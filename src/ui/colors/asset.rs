@@ -0,0 +1,283 @@
+//! Loading [`GameColorPalette`]s from `.palette.ron` and `.palette.json` asset files.
+//!
+//! Every color in the file is a hex string, for example a RON palette looks like
+//!
+//! ```ron
+//! (
+//!     dark_text: "#1F2619",
+//!     light_text: "#DAC4AF",
+//!     background: "#474340",
+//!     background_light: "#988E84",
+//!     background_dark: "#6F5A44",
+//!     accent: "#F3E9C6",
+//!     highlight: "#33476C",
+//!     interactive: "#8E5D5D",
+//!     success: "#7FA35B",
+//!     warning: "#D9A441",
+//!     danger: "#C2564A",
+//!     info: "#6F93BF",
+//!     disabled: "#857B72",
+//! )
+//! ```
+//!
+//! The status colors and `disabled` can be left out, they are then worked out from the palettes own background and text so
+//! that they stay readable on it.
+//!
+//! Set the [`ActivePalette`] to a loaded palette to use it as the [`CurrentColors`]. When bevys `file_watcher` feature is
+//! enabled, saving the file updates the [`CurrentColors`] live.
+
+use std::fmt::Display;
+
+use bevy::{
+    asset::{io::Reader, AssetEvent, AssetLoader, Assets, AsyncReadExt, Handle, LoadContext},
+    color::{Color, Oklcha, Srgba},
+    ecs::{
+        change_detection::DetectChanges,
+        event::EventReader,
+        system::{Res, ResMut, Resource},
+    },
+    math::FloatExt,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{contrast_ratio, CurrentColors, GameColorPalette, DISABLED_CHROMA};
+
+/// The Oklch hues of the status colors worked out for palettes that leave them out
+const SUCCESS_HUE: f32 = 145.0;
+const WARNING_HUE: f32 = 75.0;
+const DANGER_HUE: f32 = 25.0;
+const INFO_HUE: f32 = 250.0;
+/// The Oklch chroma of worked out status colors
+const STATUS_CHROMA: f32 = 0.14;
+/// How far from the background towards the text worked out status colors are, in Oklch lightness
+const STATUS_LIGHTNESS: f32 = 0.75;
+/// How far from the background towards the text a worked out disabled color is, in Oklch lightness
+const DISABLED_LIGHTNESS: f32 = 0.5;
+
+/// The palette used as the [`CurrentColors`], kept in sync whenever it is loaded or modified
+///
+/// Edits to the palette file on disk are only picked up with bevys `file_watcher` feature, add
+/// `bevy = { version = "0.14.1", features = ["file_watcher"] }` to your `Cargo.toml` to hot reload palettes.
+#[derive(Resource, Clone, Debug)]
+pub struct ActivePalette(pub Handle<GameColorPalette>);
+
+/// The on disk format of a [`GameColorPalette`], every color is a hex string
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PaletteDefinition {
+    pub dark_text: String,
+    pub light_text: String,
+    pub background: String,
+    pub background_light: String,
+    pub background_dark: String,
+    pub accent: String,
+    pub highlight: String,
+    pub interactive: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub danger: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<String>,
+}
+
+impl TryFrom<PaletteDefinition> for GameColorPalette {
+    type Error = PaletteLoaderError;
+
+    fn try_from(definition: PaletteDefinition) -> Result<Self, Self::Error> {
+        let dark_text = parse_color("dark_text", &definition.dark_text)?;
+        let light_text = parse_color("light_text", &definition.light_text)?;
+        let background = parse_color("background", &definition.background)?;
+        let fallback = FallbackColors::new(dark_text, light_text, background);
+        Ok(GameColorPalette {
+            dark_text,
+            light_text,
+            background,
+            background_light: parse_color("background_light", &definition.background_light)?,
+            background_dark: parse_color("background_dark", &definition.background_dark)?,
+            accent: parse_color("accent", &definition.accent)?,
+            highlight: parse_color("highlight", &definition.highlight)?,
+            interactive: parse_color("interactive", &definition.interactive)?,
+            success: parse_optional_color("success", definition.success, fallback.success)?,
+            warning: parse_optional_color("warning", definition.warning, fallback.warning)?,
+            danger: parse_optional_color("danger", definition.danger, fallback.danger)?,
+            info: parse_optional_color("info", definition.info, fallback.info)?,
+            disabled: parse_optional_color("disabled", definition.disabled, fallback.disabled)?,
+        })
+    }
+}
+
+impl From<&GameColorPalette> for PaletteDefinition {
+    fn from(palette: &GameColorPalette) -> Self {
+        let hex = |color: Color| color.to_srgba().to_hex();
+        PaletteDefinition {
+            dark_text: hex(palette.dark_text),
+            light_text: hex(palette.light_text),
+            background: hex(palette.background),
+            background_light: hex(palette.background_light),
+            background_dark: hex(palette.background_dark),
+            accent: hex(palette.accent),
+            highlight: hex(palette.highlight),
+            interactive: hex(palette.interactive),
+            success: Some(hex(palette.success)),
+            warning: Some(hex(palette.warning)),
+            danger: Some(hex(palette.danger)),
+            info: Some(hex(palette.info)),
+            disabled: Some(hex(palette.disabled)),
+        }
+    }
+}
+
+/// Stand ins for the colors a palette file left out, worked out from its background and the text that is readable on it
+struct FallbackColors {
+    success: Color,
+    warning: Color,
+    danger: Color,
+    info: Color,
+    disabled: Color,
+}
+
+impl FallbackColors {
+    fn new(dark_text: Color, light_text: Color, background: Color) -> Self {
+        let dark_contrast = contrast_ratio(dark_text, background);
+        let text = if dark_contrast > contrast_ratio(light_text, background) {
+            dark_text
+        } else {
+            light_text
+        };
+        let background = Oklcha::from(background);
+        let text = Oklcha::from(text);
+        let lightness = |amount| background.lightness.lerp(text.lightness, amount);
+        let status = |hue| Oklcha::lch(lightness(STATUS_LIGHTNESS), STATUS_CHROMA, hue).into();
+        Self {
+            success: status(SUCCESS_HUE),
+            warning: status(WARNING_HUE),
+            danger: status(DANGER_HUE),
+            info: status(INFO_HUE),
+            // Muted the same way as disabled variants of the other colors
+            disabled: Oklcha::lch(
+                lightness(DISABLED_LIGHTNESS),
+                text.chroma * DISABLED_CHROMA,
+                text.hue,
+            )
+            .into(),
+        }
+    }
+}
+
+fn parse_color(field: &'static str, value: &str) -> Result<Color, PaletteLoaderError> {
+    Srgba::hex(value)
+        .map(Into::into)
+        .map_err(|_| PaletteLoaderError::InvalidColor {
+            field,
+            value: value.to_string(),
+        })
+}
+
+fn parse_optional_color(
+    field: &'static str,
+    value: Option<String>,
+    fallback: Color,
+) -> Result<Color, PaletteLoaderError> {
+    value.map_or(Ok(fallback), |value| parse_color(field, &value))
+}
+
+/// Errors from loading a palette file
+#[derive(Debug)]
+pub enum PaletteLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Json(serde_json::Error),
+    /// A color in the file wasn't a valid hex color
+    InvalidColor {
+        field: &'static str,
+        value: String,
+    },
+}
+
+impl Display for PaletteLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaletteLoaderError::Io(error) => write!(f, "Failed to read palette: {error}"),
+            PaletteLoaderError::Ron(error) => write!(f, "Failed to parse RON palette: {error}"),
+            PaletteLoaderError::Json(error) => write!(f, "Failed to parse JSON palette: {error}"),
+            PaletteLoaderError::InvalidColor { field, value } => write!(
+                f,
+                "The palette color {field} is {value}, which isn't a valid hex color"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PaletteLoaderError {}
+
+/// Loads [`GameColorPalette`]s from `.palette.ron` and `.palette.json` files
+#[derive(Default)]
+pub struct GameColorPaletteLoader;
+
+impl AssetLoader for GameColorPaletteLoader {
+    type Asset = GameColorPalette;
+    type Settings = ();
+    type Error = PaletteLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(PaletteLoaderError::Io)?;
+
+        let is_json = load_context
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "json");
+        let definition: PaletteDefinition = match is_json {
+            true => serde_json::from_slice(&bytes).map_err(PaletteLoaderError::Json)?,
+            // Lets the optional colors be written as plain strings instead of `Some("#...")`
+            false => ron::Options::default()
+                .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+                .from_bytes(&bytes)
+                .map_err(PaletteLoaderError::Ron)?,
+        };
+        definition.try_into()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["palette.ron", "palette.json"]
+    }
+}
+
+/// Copies the [`ActivePalette`] into the [`CurrentColors`] when it is changed, loaded, or modified on disk
+pub(crate) fn sync_active_palette(
+    active_palette: Option<Res<ActivePalette>>,
+    palettes: Res<Assets<GameColorPalette>>,
+    mut events: EventReader<AssetEvent<GameColorPalette>>,
+    mut current_colors: ResMut<CurrentColors>,
+) {
+    let Some(active_palette) = active_palette else {
+        events.clear();
+        return;
+    };
+    let active_id = active_palette.0.id();
+    let mut palette_updated = false;
+    for event in events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = event {
+            palette_updated |= *id == active_id;
+        }
+    }
+    if !palette_updated && !active_palette.is_changed() {
+        return;
+    }
+
+    if let Some(palette) = palettes.get(active_id) {
+        current_colors.0 = *palette;
+    }
+}
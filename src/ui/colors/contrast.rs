@@ -0,0 +1,102 @@
+//! Checking that text in a [`GameColorPalette`] is readable against the backgrounds it is drawn on, using the
+//! [WCAG](https://www.w3.org/TR/WCAG21/#contrast-minimum) contrast ratio.
+
+use std::fmt::Display;
+
+use bevy::color::{Color, LinearRgba};
+
+use super::{GameColorPalette, PaletteRole};
+
+/// The text and background pairs that should be readable in every palette
+pub const WIDGET_TEXT_PAIRS: [(PaletteRole, PaletteRole); 6] = [
+    // The main surface that modals and panels are drawn on
+    (PaletteRole::DarkText, PaletteRole::Background),
+    (PaletteRole::LightText, PaletteRole::BackgroundDark),
+    (PaletteRole::LightText, PaletteRole::Interactive),
+    (PaletteRole::LightText, PaletteRole::Highlight),
+    (PaletteRole::DarkText, PaletteRole::BackgroundLight),
+    (PaletteRole::DarkText, PaletteRole::Accent),
+];
+
+/// A WCAG conformance level
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContrastLevel {
+    /// Level AA for large text, 18pt or 14pt bold and up
+    AaLarge,
+    /// Level AA for normal text
+    Aa,
+    /// Level AAA for normal text
+    Aaa,
+}
+
+impl ContrastLevel {
+    /// The smallest contrast ratio that meets the level
+    pub fn min_ratio(&self) -> f32 {
+        match self {
+            ContrastLevel::AaLarge => 3.0,
+            ContrastLevel::Aa => 4.5,
+            ContrastLevel::Aaa => 7.0,
+        }
+    }
+}
+
+/// A text and background pair that doesn't have enough contrast
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContrastFailure {
+    pub text: PaletteRole,
+    pub background: PaletteRole,
+    pub ratio: f32,
+    pub required: f32,
+}
+
+impl Display for ContrastFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} text on {:?} has a contrast ratio of {:.2}:1, {:.1}:1 is required",
+            self.text, self.background, self.ratio, self.required
+        )
+    }
+}
+
+/// The relative luminance of the color, 0 for black and 1 for white
+pub fn relative_luminance(color: Color) -> f32 {
+    let LinearRgba {
+        red, green, blue, ..
+    } = color.to_linear();
+    0.2126 * red + 0.7152 * green + 0.0722 * blue
+}
+
+/// The WCAG contrast ratio between two colors, from 1 for identical colors up to 21 for black on white
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let a = relative_luminance(a);
+    let b = relative_luminance(b);
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+impl GameColorPalette {
+    /// Checks the pairs that should be readable in every palette, see [`WIDGET_TEXT_PAIRS`], and returns every pair that fails the level
+    pub fn check_contrast(&self, level: ContrastLevel) -> Vec<ContrastFailure> {
+        self.check_contrast_pairs(&WIDGET_TEXT_PAIRS, level)
+    }
+
+    /// Checks the given text and background pairs and returns every pair that fails the level
+    pub fn check_contrast_pairs(
+        &self,
+        pairs: &[(PaletteRole, PaletteRole)],
+        level: ContrastLevel,
+    ) -> Vec<ContrastFailure> {
+        pairs
+            .iter()
+            .filter_map(|(text, background)| {
+                let ratio = contrast_ratio(self.color(*text), self.color(*background));
+                (ratio < level.min_ratio()).then_some(ContrastFailure {
+                    text: *text,
+                    background: *background,
+                    ratio,
+                    required: level.min_ratio(),
+                })
+            })
+            .collect()
+    }
+}
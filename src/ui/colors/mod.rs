@@ -0,0 +1,265 @@
+use bevy::{
+    app::{Plugin, PreUpdate, Update},
+    asset::{Asset, AssetApp},
+    color::{Color, Srgba},
+    ecs::{schedule::IntoSystemConfigs, system::Resource},
+    reflect::TypePath,
+};
+
+use self::{
+    asset::sync_active_palette,
+    themed::{apply_themed_colors, sync_theme_colors},
+};
+
+pub use self::{
+    asset::{ActivePalette, GameColorPaletteLoader, PaletteDefinition, PaletteLoaderError},
+    contrast::{
+        contrast_ratio, relative_luminance, ContrastFailure, ContrastLevel, WIDGET_TEXT_PAIRS,
+    },
+    themed::{ThemedBackground, ThemedBorder, ThemedText},
+    variant::{ColorVariant, DISABLED_CHROMA, HOVER_LIGHTEN, PRESSED_DARKEN},
+};
+
+mod asset;
+mod contrast;
+mod themed;
+mod variant;
+
+pub struct GameColorsPlugin {
+    pub custom_colors: Option<CurrentColors>,
+}
+
+impl Plugin for GameColorsPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        if let Some(custom_colors) = self.custom_colors {
+            app.insert_resource(custom_colors);
+        } else {
+            app.init_resource::<CurrentColors>();
+        }
+        app.init_asset::<GameColorPalette>()
+            .init_asset_loader::<GameColorPaletteLoader>()
+            .add_systems(PreUpdate, (sync_active_palette, sync_theme_colors).chain())
+            .add_systems(Update, apply_themed_colors);
+    }
+}
+
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq)]
+pub struct CurrentColors(pub GameColorPalette);
+
+impl CurrentColors {
+    /// The color with the given role
+    pub fn color(&self, role: PaletteRole) -> Color {
+        self.0.color(role)
+    }
+    /// Dark colored text
+    pub fn dark_text(&self) -> Color {
+        self.0.dark_text()
+    }
+    /// Light colored text
+    pub fn light_text(&self) -> Color {
+        self.0.light_text()
+    }
+    /// A standard background color
+    pub fn background(&self) -> Color {
+        self.0.background()
+    }
+    /// A dark colored background color
+    pub fn background_dark(&self) -> Color {
+        self.0.background_dark()
+    }
+    /// A light colored background color
+    pub fn background_light(&self) -> Color {
+        self.0.background_light()
+    }
+    /// An accent color that is alternatively used with highlight in order to direct attention. Should not by itself denote interactivity but should accent the ui
+    pub fn accent(&self) -> Color {
+        self.0.accent()
+    }
+    /// Color used to highlight something, current actions, something new, etc
+    pub fn highlight(&self) -> Color {
+        self.0.highlight()
+    }
+    /// Color used to denote interactive objcets
+    pub fn interactive(&self) -> Color {
+        self.0.interactive()
+    }
+    /// Color used to show that something went well or was completed
+    pub fn success(&self) -> Color {
+        self.0.success()
+    }
+    /// Color used to warn about something that needs attention
+    pub fn warning(&self) -> Color {
+        self.0.warning()
+    }
+    /// Color used for errors and destructive actions
+    pub fn danger(&self) -> Color {
+        self.0.danger()
+    }
+    /// Color used for neutral information
+    pub fn info(&self) -> Color {
+        self.0.info()
+    }
+    /// Color used for text and icons of things that can't be interacted with
+    pub fn disabled(&self) -> Color {
+        self.0.disabled()
+    }
+}
+
+/// A full set of ui colors. Can be loaded from a palette file, see [`GameColorPaletteLoader`]
+#[derive(Resource, Asset, TypePath, Clone, Copy, Debug, PartialEq)]
+pub struct GameColorPalette {
+    pub dark_text: Color,
+    pub light_text: Color,
+    pub background: Color,
+    pub background_light: Color,
+    pub background_dark: Color,
+    pub accent: Color,
+    pub highlight: Color,
+    pub interactive: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub danger: Color,
+    pub info: Color,
+    pub disabled: Color,
+}
+
+impl Default for GameColorPalette {
+    fn default() -> Self {
+        GameColorPalette::dark()
+    }
+}
+
+/// Names a color in the [`GameColorPalette`] so that it can be looked up again when the palette changes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PaletteRole {
+    DarkText,
+    LightText,
+    Background,
+    BackgroundLight,
+    BackgroundDark,
+    Accent,
+    Highlight,
+    Interactive,
+    Success,
+    Warning,
+    Danger,
+    Info,
+    Disabled,
+}
+
+impl GameColorPalette {
+    /// The color of the palette with the given role
+    pub fn color(&self, role: PaletteRole) -> Color {
+        match role {
+            PaletteRole::DarkText => self.dark_text,
+            PaletteRole::LightText => self.light_text,
+            PaletteRole::Background => self.background,
+            PaletteRole::BackgroundLight => self.background_light,
+            PaletteRole::BackgroundDark => self.background_dark,
+            PaletteRole::Accent => self.accent,
+            PaletteRole::Highlight => self.highlight,
+            PaletteRole::Interactive => self.interactive,
+            PaletteRole::Success => self.success,
+            PaletteRole::Warning => self.warning,
+            PaletteRole::Danger => self.danger,
+            PaletteRole::Info => self.info,
+            PaletteRole::Disabled => self.disabled,
+        }
+    }
+
+    pub fn dark() -> GameColorPalette {
+        Self {
+            dark_text: Color::srgb(0.12, 0.15, 0.10),
+            light_text: Srgba::hex("#DAC4AF").unwrap().into(),
+            background: Srgba::hex("#474340").unwrap().into(),
+            background_light: Srgba::hex("#988E84").unwrap().into(),
+            background_dark: Srgba::hex("#6F5A44").unwrap().into(),
+            accent: Srgba::hex("#F3E9C6").unwrap().into(),
+            highlight: Srgba::hex("33476C").unwrap().into(), //Color::rgb(0.07, 0.36, 0.62),
+            interactive: Srgba::hex("#8E5D5D").unwrap().into(),
+            success: Srgba::hex("#7FA35B").unwrap().into(),
+            warning: Srgba::hex("#D9A441").unwrap().into(),
+            danger: Srgba::hex("#C2564A").unwrap().into(),
+            info: Srgba::hex("#6F93BF").unwrap().into(),
+            disabled: Srgba::hex("#857B72").unwrap().into(),
+        }
+    }
+
+    /// Dark text on light backgrounds. Passes WCAG AA, see [`GameColorPalette::check_contrast`].
+    pub fn light() -> GameColorPalette {
+        Self {
+            dark_text: Srgba::hex("#1F2328").unwrap().into(),
+            light_text: Srgba::hex("#FAF8F5").unwrap().into(),
+            background: Srgba::hex("#EDE8E1").unwrap().into(),
+            background_light: Srgba::hex("#FFFFFF").unwrap().into(),
+            background_dark: Srgba::hex("#4E5968").unwrap().into(),
+            accent: Srgba::hex("#E9B949").unwrap().into(),
+            highlight: Srgba::hex("#1F5C99").unwrap().into(),
+            interactive: Srgba::hex("#8C3B4A").unwrap().into(),
+            success: Srgba::hex("#2E7D32").unwrap().into(),
+            warning: Srgba::hex("#A35F00").unwrap().into(),
+            danger: Srgba::hex("#C62828").unwrap().into(),
+            info: Srgba::hex("#1565C0").unwrap().into(),
+            disabled: Srgba::hex("#9A9A9A").unwrap().into(),
+        }
+    }
+
+    /// Black text on white with strong accents, for players who need as much contrast as possible. Passes WCAG AAA.
+    pub fn high_contrast() -> GameColorPalette {
+        Self {
+            dark_text: Color::BLACK,
+            light_text: Color::WHITE,
+            background: Srgba::hex("#F0F0F0").unwrap().into(),
+            background_light: Color::WHITE,
+            background_dark: Srgba::hex("#1A1A1A").unwrap().into(),
+            accent: Srgba::hex("#FFE600").unwrap().into(),
+            highlight: Srgba::hex("#0033B3").unwrap().into(),
+            interactive: Srgba::hex("#6B0000").unwrap().into(),
+            success: Srgba::hex("#005500").unwrap().into(),
+            warning: Srgba::hex("#6B3D00").unwrap().into(),
+            danger: Srgba::hex("#990018").unwrap().into(),
+            info: Srgba::hex("#00468C").unwrap().into(),
+            disabled: Srgba::hex("#707070").unwrap().into(),
+        }
+    }
+
+    pub fn dark_text(&self) -> Color {
+        self.dark_text
+    }
+    pub fn light_text(&self) -> Color {
+        self.light_text
+    }
+    pub fn background(&self) -> Color {
+        self.background
+    }
+    pub fn background_light(&self) -> Color {
+        self.background_light
+    }
+    pub fn accent(&self) -> Color {
+        self.accent
+    }
+    pub fn background_dark(&self) -> Color {
+        self.background_dark
+    }
+    pub fn highlight(&self) -> Color {
+        self.highlight
+    }
+    pub fn interactive(&self) -> Color {
+        self.interactive
+    }
+    pub fn success(&self) -> Color {
+        self.success
+    }
+    pub fn warning(&self) -> Color {
+        self.warning
+    }
+    pub fn danger(&self) -> Color {
+        self.danger
+    }
+    pub fn info(&self) -> Color {
+        self.info
+    }
+    pub fn disabled(&self) -> Color {
+        self.disabled
+    }
+}
@@ -0,0 +1,74 @@
+//! Components that record which [`PaletteRole`] a color came from so that it can be re-applied when the [`CurrentColors`]
+//! change.
+//!
+//! Widgets insert these on the entities they spawn, add them to your own ui to have it follow theme changes too.
+
+use bevy::{
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        system::{Query, Res, ResMut},
+        world::Ref,
+    },
+    text::Text,
+    ui::{BackgroundColor, BorderColor},
+};
+
+use crate::ui::theme::Theme;
+
+use super::{CurrentColors, PaletteRole};
+
+/// Keeps the [`BackgroundColor`] of the entity set to the given palette color
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThemedBackground(pub PaletteRole);
+
+/// Keeps the [`BorderColor`] of the entity set to the given palette color
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThemedBorder(pub PaletteRole);
+
+/// Keeps the color of every section of the entities [`Text`] set to the given palette color
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThemedText(pub PaletteRole);
+
+/// Re-applies the palette colors of every themed entity when the [`CurrentColors`] or its role changes
+pub(crate) fn apply_themed_colors(
+    colors: Res<CurrentColors>,
+    mut backgrounds: Query<(Ref<ThemedBackground>, &mut BackgroundColor)>,
+    mut borders: Query<(Ref<ThemedBorder>, &mut BorderColor)>,
+    mut texts: Query<(Ref<ThemedText>, &mut Text)>,
+) {
+    let colors_changed = colors.is_changed();
+
+    for (themed, mut background) in backgrounds.iter_mut() {
+        if colors_changed || themed.is_changed() {
+            background.0 = colors.color(themed.0);
+        }
+    }
+    for (themed, mut border) in borders.iter_mut() {
+        if colors_changed || themed.is_changed() {
+            border.0 = colors.color(themed.0);
+        }
+    }
+    for (themed, mut text) in texts.iter_mut() {
+        if colors_changed || themed.is_changed() {
+            for section in text.sections.iter_mut() {
+                section.style.color = colors.color(themed.0);
+            }
+        }
+    }
+}
+
+/// Keeps the [`CurrentColors`] and the colors of the [`Theme`] the same, copying whichever one changed into the other
+pub(crate) fn sync_theme_colors(mut colors: ResMut<CurrentColors>, theme: Option<ResMut<Theme>>) {
+    let Some(mut theme) = theme else {
+        return;
+    };
+    if theme.colors == *colors {
+        return;
+    }
+    if colors.is_changed() || !theme.is_changed() {
+        theme.colors = *colors;
+    } else {
+        *colors = theme.colors;
+    }
+}
@@ -0,0 +1,56 @@
+//! Shades of palette colors for the different states of interactive ui.
+//!
+//! The shades are computed in [`Oklcha`] so that lightening and darkening look the same amount brighter or darker no
+//! matter the hue of the color they are applied to.
+
+use bevy::color::{Color, Luminance, Oklcha};
+
+use super::{CurrentColors, GameColorPalette, PaletteRole};
+
+/// How much lighter a hovered color is, in Oklch lightness
+pub const HOVER_LIGHTEN: f32 = 0.08;
+/// How much darker a pressed color is, in Oklch lightness
+pub const PRESSED_DARKEN: f32 = 0.08;
+/// How much of the chroma a disabled color keeps
+pub const DISABLED_CHROMA: f32 = 0.2;
+
+/// A shade of a palette color
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorVariant {
+    /// The color as it is in the palette
+    #[default]
+    Base,
+    /// Lightened
+    Hovered,
+    /// Darkened
+    Pressed,
+    /// Desaturated
+    Disabled,
+}
+
+impl ColorVariant {
+    /// Derives this variant from the given color
+    pub fn apply(&self, color: Color) -> Color {
+        let oklcha = Oklcha::from(color);
+        match self {
+            ColorVariant::Base => color,
+            ColorVariant::Hovered => oklcha.lighter(HOVER_LIGHTEN).into(),
+            ColorVariant::Pressed => oklcha.darker(PRESSED_DARKEN).into(),
+            ColorVariant::Disabled => oklcha.with_chroma(oklcha.chroma * DISABLED_CHROMA).into(),
+        }
+    }
+}
+
+impl GameColorPalette {
+    /// The given variant of the color with the given role
+    pub fn color_variant(&self, role: PaletteRole, variant: ColorVariant) -> Color {
+        variant.apply(self.color(role))
+    }
+}
+
+impl CurrentColors {
+    /// The given variant of the color with the given role
+    pub fn color_variant(&self, role: PaletteRole, variant: ColorVariant) -> Color {
+        self.0.color_variant(role, variant)
+    }
+}
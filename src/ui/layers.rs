@@ -0,0 +1,106 @@
+//! Named layers that ui is drawn on.
+//!
+//! Give a root ui node a [`UiLayer`] component and its [`ZIndex`] is kept at the global z of that layer, so everything on a
+//! higher layer is always drawn over it no matter which scene or widget spawned it. The order of the layers is set by the
+//! [`UiLayers`] resource.
+
+use bevy::{
+    app::{Plugin, PostUpdate},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        schedule::IntoSystemConfigs,
+        system::{Query, Res, Resource},
+        world::Ref,
+    },
+    ui::{UiSystem, ZIndex},
+};
+
+/// How far apart each layer is in global z. Offsets within a layer should stay below this.
+pub const UI_LAYER_SPACING: i32 = 100;
+
+pub struct UiLayersPlugin;
+
+impl Plugin for UiLayersPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<UiLayers>();
+        app.add_systems(PostUpdate, apply_ui_layers.before(UiSystem::Layout));
+    }
+}
+
+/// A layer of the ui. Insert it on a root ui node to draw that node and all of its children on the layer.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UiLayer {
+    /// Always on screen gameplay ui
+    Hud,
+    /// Menus and scenes that take over the screen
+    Menu,
+    /// Modals that block everything below them
+    Modal,
+    /// Tooltips, drawn over modals so modal content can have them
+    Tooltip,
+    /// Short lived notifications
+    Toast,
+    /// Debug overlays, drawn over everything
+    Debug,
+}
+
+/// The order the [`UiLayer`]s are drawn in
+#[derive(Resource, Clone, Debug)]
+pub struct UiLayers {
+    /// Every layer from the bottom to the top
+    order: Vec<UiLayer>,
+}
+
+impl Default for UiLayers {
+    fn default() -> Self {
+        Self {
+            order: vec![
+                UiLayer::Hud,
+                UiLayer::Menu,
+                UiLayer::Modal,
+                UiLayer::Tooltip,
+                UiLayer::Toast,
+                UiLayer::Debug,
+            ],
+        }
+    }
+}
+
+impl UiLayers {
+    /// Creates a new layer order from the bottom to the top. Layers that are left out are drawn over all of the given ones.
+    pub fn new(order: impl IntoIterator<Item = UiLayer>) -> Self {
+        Self {
+            order: order.into_iter().collect(),
+        }
+    }
+
+    /// Every layer from the bottom to the top
+    pub fn order(&self) -> &[UiLayer] {
+        &self.order
+    }
+
+    /// The global z of the given layer
+    pub fn global_z(&self, layer: UiLayer) -> i32 {
+        let position = self
+            .order
+            .iter()
+            .position(|ordered| *ordered == layer)
+            .unwrap_or(self.order.len());
+        (position as i32 + 1) * UI_LAYER_SPACING
+    }
+
+    /// The [`ZIndex`] of the given layer, raised by the offset to order things within the layer
+    pub fn z_index(&self, layer: UiLayer, offset: i32) -> ZIndex {
+        ZIndex::Global(self.global_z(layer) + offset)
+    }
+}
+
+/// Keeps the [`ZIndex`] of every entity on a layer up to date with the [`UiLayers`]
+fn apply_ui_layers(layers: Res<UiLayers>, mut query: Query<(Ref<UiLayer>, &mut ZIndex)>) {
+    for (layer, mut z_index) in query.iter_mut() {
+        if layers.is_changed() || layer.is_changed() {
+            *z_index = layers.z_index(*layer, 0);
+        }
+    }
+}
@@ -2,8 +2,7 @@ use bevy::{
     app::{Plugin, Update},
     ecs::{
         query::With,
-        schedule::{common_conditions::resource_exists, IntoSystemConfigs},
-        system::{Local, Query, ResMut, Resource},
+        system::{Local, Query, Res, ResMut, Resource},
     },
     math::Vec2,
     ui::UiScale,
@@ -13,11 +12,15 @@ use bevy_simple_text_input::TextInputPlugin;
 
 use self::{
     colors::{CurrentColors, GameColorsPlugin},
+    layers::{UiLayersPlugin, UI_LAYER_SPACING},
+    theme::ThemePlugin,
     widgets::WidgetsPlugin,
 };
 
 pub mod colors;
+pub mod layers;
 pub mod scenes;
+pub mod theme;
 pub mod widgets;
 
 pub struct NsCoreUiPlugin {
@@ -29,38 +32,147 @@ impl Plugin for NsCoreUiPlugin {
         app.add_plugins(TextInputPlugin);
         app.add_plugins((
             WidgetsPlugin,
+            UiLayersPlugin,
             GameColorsPlugin {
-                custom_colors: self.custom_colors.clone(),
+                custom_colors: self.custom_colors,
             },
+            ThemePlugin,
         ));
-        app.add_systems(Update, scale.run_if(resource_exists::<ScaleUi>));
+        app.add_systems(Update, scale);
     }
 }
 
-pub const UI_SCREEN_LAYER: i32 = 1;
-pub const UI_MODAL_LAYER: i32 = 100;
+/// The global z of [`UiLayer::Menu`](layers::UiLayer::Menu) with the default [`UiLayers`](layers::UiLayers) order. This
+/// used to be 1 and is now 200.
+#[deprecated(note = "give the root node a `UiLayer::Menu` component, or use `UiLayers::global_z`")]
+pub const UI_SCREEN_LAYER: i32 = 2 * UI_LAYER_SPACING;
+/// The global z of [`UiLayer::Modal`](layers::UiLayer::Modal) with the default [`UiLayers`](layers::UiLayers) order. This
+/// used to be 100 and is now 300.
+#[deprecated(note = "give the root node a `UiLayer::Modal` component, or use `UiLayers::global_z`")]
+pub const UI_MODAL_LAYER: i32 = 3 * UI_LAYER_SPACING;
 
-#[derive(Resource, Default)]
-pub struct ScaleUi;
+/// Automatically scales the ui to the primary window. Insert this resource to turn scaling on.
+///
+/// The default scales the ui to fit a 1920x1080 reference resolution inside the window. `ScaleUi` used to be a unit struct
+/// with exactly that behavior, so code that inserted `ScaleUi` should insert `ScaleUi::default()` instead.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct ScaleUi {
+    /// The window size the ui was designed at, the ui is drawn at a scale of 1 at this size
+    pub reference_resolution: Vec2,
+    pub mode: ScaleUiMode,
+    /// The smallest scale the ui can be shrunk to
+    pub min: Option<f32>,
+    /// The largest scale the ui can be grown to
+    pub max: Option<f32>,
+}
+
+impl Default for ScaleUi {
+    fn default() -> Self {
+        Self {
+            reference_resolution: Vec2::new(1920.0, 1080.0),
+            mode: ScaleUiMode::Fit,
+            min: None,
+            max: None,
+        }
+    }
+}
+
+/// How the window size is compared against the [`ScaleUi::reference_resolution`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScaleUiMode {
+    /// Scales so the whole reference resolution fits inside the window, using the smaller of the two axes
+    #[default]
+    Fit,
+    /// Scales so the reference resolution covers the whole window, using the larger of the two axes
+    Fill,
+    /// Scales so the reference width matches the window width
+    MatchWidth,
+    /// Scales so the reference height matches the window height
+    MatchHeight,
+    /// Like [`ScaleUiMode::Fit`] but rounded down to whole steps so pixel art stays crisp. Never goes below 1.
+    IntegerFit,
+    /// Like [`ScaleUiMode::Fit`], but sized for the scale factor the operating systems display scaling asks for. If the window
+    /// overrides its scale factor the ui is scaled by the difference, so it stays the size the display scaling intends.
+    DpiAware,
+}
+
+impl ScaleUi {
+    /// The ui scale for the given window
+    pub fn scale_for(&self, window: &Window) -> f32 {
+        let scale = Vec2::new(window.width(), window.height()) / self.reference_resolution;
+        let scale = match self.mode {
+            ScaleUiMode::Fit => scale.min_element(),
+            ScaleUiMode::Fill => scale.max_element(),
+            ScaleUiMode::MatchWidth => scale.x,
+            ScaleUiMode::MatchHeight => scale.y,
+            ScaleUiMode::IntegerFit => scale.min_element().floor().max(1.0),
+            // Ui is laid out in logical pixels, which only follow the display scaling if the window doesn't override it
+            ScaleUiMode::DpiAware => {
+                scale.min_element() * window.resolution.base_scale_factor() / window.scale_factor()
+            }
+        };
+        scale
+            .max(self.min.unwrap_or(f32::MIN))
+            .min(self.max.unwrap_or(f32::MAX))
+    }
+}
 
-/// Scales ui to match the screen
+/// A multiplier on top of the automatic [`ScaleUi`] scale, such as a players ui size setting. Insert it to use it, it can be
+/// changed at any time and the ui is rescaled straight away.
+///
+/// It is applied after [`ScaleUi::min`] and [`ScaleUi::max`] so players can always make the ui bigger or smaller. Without a
+/// [`ScaleUi`] it is used as the ui scale directly.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct UserUiScale(pub f32);
+
+impl Default for UserUiScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// What the ui scale was last worked out from, see [`scale`]
+#[derive(PartialEq)]
+pub struct ScaleInputs {
+    window_size: Vec2,
+    scale_factor: f32,
+    settings: Option<ScaleUi>,
+    user_scale: Option<UserUiScale>,
+}
+
+/// Scales ui to match the screen according to the [`ScaleUi`] and [`UserUiScale`] resources.
+///
+/// Removing both resources puts the [`UiScale`] back to 1, while the [`UiScale`] is left alone if neither was ever inserted.
 pub fn scale(
-    mut cached_size: Local<Vec2>,
+    mut cached: Local<Option<ScaleInputs>>,
+    settings: Option<Res<ScaleUi>>,
+    user_scale: Option<Res<UserUiScale>>,
     mut ui_scale: ResMut<UiScale>,
     windows: Query<&Window, With<PrimaryWindow>>,
 ) {
     let Some(primary) = windows.iter().next() else {
         return;
     };
-    let ww = primary.width();
-    let wh = primary.height();
-    if cached_size.x == ww && cached_size.y == wh {
+    let inputs = ScaleInputs {
+        window_size: Vec2::new(primary.width(), primary.height()),
+        scale_factor: primary.scale_factor(),
+        settings: settings.as_deref().copied(),
+        user_scale: user_scale.as_deref().copied(),
+    };
+    // Comparing everything the scale depends on, instead of checking for changes, also catches the resources being removed
+    if cached.as_ref() == Some(&inputs) {
+        return;
+    }
+    let was_scaled = cached
+        .as_ref()
+        .is_some_and(|cached| cached.settings.is_some() || cached.user_scale.is_some());
+    let is_scaled = inputs.settings.is_some() || inputs.user_scale.is_some();
+    *cached = Some(inputs);
+    if !was_scaled && !is_scaled {
         return;
     }
-    cached_size.x = ww;
-    cached_size.y = wh;
 
-    let scale_h = ww / 1920.0;
-    let scale_w = wh / 1080.0;
-    ui_scale.0 = scale_h.min(scale_w);
+    let auto_scale = settings.map_or(1.0, |settings| settings.scale_for(primary));
+    let user_scale = user_scale.map_or(1.0, |user_scale| user_scale.0);
+    ui_scale.0 = auto_scale * user_scale;
 }
@@ -0,0 +1,116 @@
+//! Typed data handed to a scenes setup system when the scene is entered.
+//!
+//! Supply the input with [`SceneInputCommandsExt::transition_with_input`] (or [`SceneInputCommandsExt::set_scene_input`] if
+//! you are changing state some other way) and the setup system of a scene added with
+//! [`add_scene_with_input`](super::ScenesAppExtension::add_scene_with_input) will receive it as [`In<T>`](bevy::ecs::system::In).
+//! The input is keyed by the state it was supplied for, so only a scene entered with that state receives it. Input that no
+//! scene used is removed after the next transition of that state type, so it can never leak into a later transition.
+
+use std::any::Any;
+
+use bevy::{
+    ecs::{
+        event::EventReader,
+        system::{Commands, Res, Resource},
+    },
+    log::warn,
+    prelude::{NextState, StateTransitionEvent, States, World},
+    state::state::FreelyMutableState,
+};
+
+use super::scene_setup_system;
+
+/// The input that will be handed to the setup system that takes a `T` of the scene entered with the given state
+#[derive(Resource)]
+pub struct SceneInput<T: Send + Sync + 'static> {
+    pub input: T,
+    /// The state the input was supplied for
+    target: Box<dyn Any + Send + Sync>,
+}
+
+impl<T: Send + Sync + 'static> SceneInput<T> {
+    /// Creates input for the scene entered with the given state
+    pub fn new<S: States>(state: S, input: T) -> Self {
+        Self {
+            input,
+            target: Box::new(state),
+        }
+    }
+
+    /// Returns true if the input was supplied for the given state
+    pub fn is_for<S: States>(&self, state: &S) -> bool {
+        self.target.downcast_ref::<S>() == Some(state)
+    }
+
+    fn is_for_state_type<S: States>(&self) -> bool {
+        self.target.is::<S>()
+    }
+}
+
+/// Commands to supply typed input to scene setup systems
+pub trait SceneInputCommandsExt {
+    /// Transitions to the given state, handing the input to the setup system of the scene being entered
+    fn transition_with_input<S: FreelyMutableState, T: Send + Sync + 'static>(
+        &mut self,
+        state: S,
+        input: T,
+    );
+
+    /// Stores the input for the scene entered with the given state, replacing any input that hasn't been used yet. The state
+    /// has to be entered by the next transition of its type.
+    fn set_scene_input<S: States, T: Send + Sync + 'static>(&mut self, state: S, input: T);
+}
+
+impl SceneInputCommandsExt for Commands<'_, '_> {
+    fn transition_with_input<S: FreelyMutableState, T: Send + Sync + 'static>(
+        &mut self,
+        state: S,
+        input: T,
+    ) {
+        self.insert_resource(SceneInput::new(state.clone(), input));
+        self.add(move |world: &mut World| {
+            world.resource_mut::<NextState<S>>().set(state);
+        });
+    }
+
+    fn set_scene_input<S: States, T: Send + Sync + 'static>(&mut self, state: S, input: T) {
+        self.insert_resource(SceneInput::new(state, input));
+    }
+}
+
+/// Takes the current [`SceneInput<T>`] out of the world if it was supplied for the entered state, or the default value
+/// otherwise, and runs the setup system
+pub(crate) fn run_setup_with_input<S: States, T: Default + Send + Sync + 'static>(
+    world: &mut World,
+    scene: &'static str,
+    state: &S,
+) {
+    let Some(system_id) = scene_setup_system::<T, ()>(world, scene) else {
+        return;
+    };
+    let supplied = world
+        .get_resource::<SceneInput<T>>()
+        .is_some_and(|input| input.is_for(state));
+    let input = supplied
+        .then(|| world.remove_resource::<SceneInput<T>>())
+        .flatten()
+        .map(|input| input.input)
+        .unwrap_or_default();
+    if let Err(error) = world.run_system_with_input(system_id, input) {
+        warn!("Failed to set up scene {scene}: {error}");
+    }
+}
+
+/// Removes input supplied for a state of type `S` that wasn't used by the scene entered during the last transition
+pub(crate) fn clear_unused_scene_input<S: States, T: Send + Sync + 'static>(
+    mut transitions: EventReader<StateTransitionEvent<S>>,
+    input: Option<Res<SceneInput<T>>>,
+    mut commands: Commands,
+) {
+    if transitions.read().last().is_none() {
+        return;
+    }
+    if input.is_some_and(|input| input.is_for_state_type::<S>()) {
+        commands.remove_resource::<SceneInput<T>>();
+    }
+}
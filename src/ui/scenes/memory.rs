@@ -0,0 +1,127 @@
+//! Opt in memory of widget state for scenes.
+//!
+//! When a remembered scene is left the state of its widgets is snapshotted and the next time the scene is set up that state is
+//! restored onto the freshly spawned widgets. Widgets are matched up by the order they appear in the scenes hierarchy, so the
+//! setup system needs to build the same structure every time for the state to land on the right widgets.
+//!
+//! Currently remembers the open tab of [`tabbed_content`](crate::ui::widgets::tabbed_content), the position of scroll
+//! containers, and the contents of text inputs.
+
+use bevy::{
+    ecs::{component::Component, system::Resource, world::World},
+    reflect::TypePath,
+    ui::{Style, Val},
+    utils::{HashMap, HashSet},
+};
+use bevy_eventlistener::event_listener::On;
+use bevy_simple_text_input::TextInputValue;
+
+use super::{scene_hierarchy, scene_roots};
+use crate::ui::widgets::{
+    button::SelectedButton,
+    scroll::{ScrollEvent, Scrollable},
+    tabbed_content::{select_tab, TabbedContent},
+};
+
+/// Snapshots of the widget state of every scene that opted in to being remembered
+#[derive(Resource, Default)]
+pub struct SceneMemory {
+    remembered: HashSet<&'static str>,
+    snapshots: HashMap<&'static str, SceneSnapshot>,
+}
+
+impl SceneMemory {
+    /// Marks the scene with the given marker component to be remembered
+    pub fn remember<Marker: Component + TypePath>(&mut self) {
+        self.remembered.insert(Marker::type_path());
+    }
+
+    /// Throws away the snapshot of the given scene so that it is built from scratch the next time it is set up
+    pub fn forget<Marker: Component + TypePath>(&mut self) {
+        self.snapshots.remove(Marker::type_path());
+    }
+
+    /// Throws away every snapshot
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+}
+
+#[derive(Default)]
+struct SceneSnapshot {
+    open_tabs: Vec<Option<usize>>,
+    scroll_positions: Vec<f32>,
+    text_inputs: Vec<String>,
+}
+
+/// Snapshots the widget state of the scene if it is remembered
+pub(crate) fn snapshot_scene<Marker: Component + TypePath>(world: &mut World) {
+    if !world
+        .get_resource::<SceneMemory>()
+        .is_some_and(|memory| memory.remembered.contains(Marker::type_path()))
+    {
+        return;
+    }
+
+    let mut snapshot = SceneSnapshot::default();
+    let roots = scene_roots::<Marker>(world);
+    for entity in scene_hierarchy(world, roots) {
+        if let Some(tabbed_content) = world.get::<TabbedContent>(entity) {
+            let open_tab = tabbed_content
+                .buttons()
+                .iter()
+                .position(|button| world.get::<SelectedButton>(*button).is_some());
+            snapshot.open_tabs.push(open_tab);
+        }
+        if world.get::<On<ScrollEvent>>(entity).is_some() {
+            let position = world
+                .get::<Scrollable>(entity)
+                .map(|scrollable| scrollable.position())
+                .unwrap_or_default();
+            snapshot.scroll_positions.push(position);
+        }
+        if let Some(value) = world.get::<TextInputValue>(entity) {
+            snapshot.text_inputs.push(value.0.clone());
+        }
+    }
+
+    world
+        .resource_mut::<SceneMemory>()
+        .snapshots
+        .insert(Marker::type_path(), snapshot);
+}
+
+/// Restores the last snapshot of the scene onto its widgets if it has one
+pub(crate) fn restore_scene<Marker: Component + TypePath>(world: &mut World) {
+    let Some(snapshot) = world
+        .get_resource_mut::<SceneMemory>()
+        .and_then(|mut memory| memory.snapshots.remove(Marker::type_path()))
+    else {
+        return;
+    };
+
+    let mut open_tabs = snapshot.open_tabs.into_iter();
+    let mut scroll_positions = snapshot.scroll_positions.into_iter();
+    let mut text_inputs = snapshot.text_inputs.into_iter();
+    let roots = scene_roots::<Marker>(world);
+    for entity in scene_hierarchy(world, roots) {
+        if world.get::<TabbedContent>(entity).is_some() {
+            if let Some(Some(open_tab)) = open_tabs.next() {
+                select_tab(world, entity, open_tab);
+            }
+        }
+        if world.get::<On<ScrollEvent>>(entity).is_some() {
+            if let Some(position) = scroll_positions.next() {
+                world.entity_mut(entity).insert(Scrollable::new(position));
+                if let Some(mut style) = world.get_mut::<Style>(entity) {
+                    style.top = Val::Px(position);
+                }
+            }
+        }
+        if let Some(mut value) = world.get_mut::<TextInputValue>(entity) {
+            if let Some(text) = text_inputs.next() {
+                value.0 = text;
+            }
+        }
+    }
+}
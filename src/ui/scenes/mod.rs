@@ -3,17 +3,69 @@
 //! They represent a logical point in the app delineated by the state fed into it where you want the given ui to be displayed.
 //! Internally the plugin will manage spawning and despawning the scene and all the components marked with the given marker componet
 //! from the app when the scene is entered or left
+//!
+//! Overlay scenes are not tied to a state and are instead pushed and popped on the [`SceneStack`]
 
 use bevy::{
-    app::App, ecs::{
+    app::{App, Update},
+    ecs::{
         component::Component,
         entity::Entity,
+        event::{EventReader, EventWriter},
         query::With,
-        system::{Commands, IntoSystem, Query, Res},
-    }, hierarchy::DespawnRecursiveExt, prelude::{OnEnter, OnExit, States}, reflect::TypePath
+        schedule::IntoSystemConfigs,
+        system::{Commands, IntoSystem, Res, Resource, SystemId},
+        world::World,
+    },
+    hierarchy::{Children, DespawnRecursiveExt, Parent},
+    log::warn,
+    prelude::{
+        EnterSchedules, ExitSchedules, OnEnter, OnExit, StateTransition, StateTransitionEvent,
+        States,
+    },
+    reflect::TypePath,
+    state::state::setup_state_transitions_in_world,
+    utils::{HashMap, HashSet},
 };
 
-use crate::one_shot_system::{MarkerComponent, OneShotSystemIds};
+use self::{
+    input::{clear_unused_scene_input, run_setup_with_input},
+    memory::{restore_scene, snapshot_scene},
+    sliced::{build_scene_slices, cancel_scene_build, start_scene_build, SceneBuilds},
+    transitions::{animate_scene_transitions, enter_scene, exit_scene, SceneTransitionSettings},
+};
+use crate::one_shot_system::{
+    insert_system_id, report_error, MarkerComponent, OneShotSystemError, OneShotSystemIds,
+    OneShotSystemWorldExt,
+};
+
+pub use self::{
+    input::{SceneInput, SceneInputCommandsExt},
+    memory::SceneMemory,
+    router::{SceneNavigator, SceneRouter, SceneRouterAppExtension},
+    sliced::{SceneBuildProgress, SceneReady, SceneSlice},
+    stack::{LowerSceneBehavior, SceneStack, SceneStackCommandsExt},
+    transitions::{SceneAnimation, SceneTransition, SceneTransitions, SlideEdge},
+};
+
+mod input;
+mod memory;
+mod router;
+mod sliced;
+mod stack;
+mod transitions;
+
+/// The label scene setup systems are registered under in the [`OneShotSystemIds`] registry
+pub const SCENE_SETUP_LABEL: &str = "scene_setup";
+/// The label scene teardown systems are registered under in the [`OneShotSystemIds`] registry
+pub const SCENE_TEARDOWN_LABEL: &str = "scene_teardown";
+
+/// How to find the roots of every scene, keyed by the scene marker components [`TypePath`], so that spawned scenes can be
+/// found without knowing their marker components
+#[derive(Resource, Default)]
+pub(crate) struct SceneRootQueries {
+    pub(crate) map: HashMap<&'static str, fn(&mut World) -> Vec<Entity>>,
+}
 
 pub trait ScenesAppExtension {
     /// Adds a new scene that will run the setup system every time the given state is entered and a cleanup system every time it leaves.
@@ -23,6 +75,82 @@ pub trait ScenesAppExtension {
         setup_system: impl IntoSystem<(), (), M> + 'static,
         state: impl States,
     );
+
+    /// Adds a new scene whose setup system takes a typed input, supplied with [`SceneInputCommandsExt::transition_with_input`].
+    ///
+    /// If no input was supplied when the state is entered the setup system receives `T::default()`.
+    fn add_scene_with_input<Marker: Component + TypePath, T: Default + Send + Sync + 'static, M>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<T, (), M> + 'static,
+        state: impl States,
+    );
+
+    /// Adds a new scene that is built over multiple frames instead of all at once.
+    ///
+    /// The setup system is run once per frame with a [`SceneSlice`] of at most `budget` items to build, until it returns
+    /// [`SceneBuildProgress::Complete`]. The scene is hidden until then and a [`SceneReady`] event is sent once it is shown.
+    fn add_sliced_scene<Marker: Component + TypePath, M>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<SceneSlice, SceneBuildProgress, M> + 'static,
+        budget: usize,
+        state: impl States,
+    );
+
+    /// Adds a new scene that is spawned when entering any of the given states from a state outside of them and despawned when
+    /// leaving all of them. Moving between states inside of the set leaves the scene untouched.
+    fn add_scene_in_states<Marker: Component + TypePath, M, S: States>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<(), (), M> + 'static,
+        states: impl IntoIterator<Item = S>,
+    );
+
+    /// Adds a new scene that is spawned when entering a state that matches the given predicate from one that doesn't, and
+    /// despawned when leaving to a state that doesn't match.
+    ///
+    /// For more complex cases a [`ComputedStates`](bevy::prelude::ComputedStates) can be used with [`add_scene`](ScenesAppExtension::add_scene) instead.
+    fn add_scene_when<Marker: Component + TypePath, M, S: States>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<(), (), M> + 'static,
+        predicate: impl Fn(&S) -> bool + Clone + Send + Sync + 'static,
+    );
+
+    /// Adds a new overlay scene that is not tied to any state. Overlay scenes are spawned by pushing them onto the
+    /// [`SceneStack`] with [`SceneStackCommandsExt::push_scene`] and despawned when they are popped off of it.
+    fn add_overlay_scene<Marker: Component + TypePath, M>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<(), (), M> + 'static,
+    );
+
+    /// Remembers the widget state of the scene marked with the given marker component when it is left and restores it the next
+    /// time it is set up. See [`SceneMemory`] for what is remembered.
+    fn remember_scene_state<Marker: Component + TypePath>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+    );
+
+    /// Sets the animations the scene marked with the given marker component plays when it is entered and left.
+    ///
+    /// When the scene is left its roots are kept alive until the exit animation has finished.
+    fn set_scene_transitions<Marker: Component + TypePath>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        transitions: SceneTransitions,
+    );
+
+    /// Adds a teardown system to the scene marked with the given marker component.
+    ///
+    /// The teardown system runs every time the scene is left, before any of the scenes entities are despawned. Use it to save
+    /// state, release resources, or undo anything the setup system changed outside of the scene.
+    fn add_scene_teardown<Marker: Component + TypePath, M>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        teardown_system: impl IntoSystem<(), (), M> + 'static,
+    );
 }
 
 impl ScenesAppExtension for App {
@@ -33,26 +161,341 @@ impl ScenesAppExtension for App {
         states: impl States,
     ) {
         let system_id = self.world_mut().register_system(setup_system);
-        let mut resource = self.world_mut().resource_mut::<OneShotSystemIds>();
-        resource.map.insert(Marker::type_path(), system_id);
+        register_scene_setup::<Marker, _, _>(self, system_id);
         self.add_systems(OnEnter(states.clone()), setup_scene::<Marker>);
         self.add_systems(OnExit(states), cleanup_scene::<Marker>);
     }
+
+    fn add_scene_with_input<Marker: Component + TypePath, T: Default + Send + Sync + 'static, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<T, (), M> + 'static,
+        state: impl States,
+    ) {
+        let system_id = self.world_mut().register_system(setup_system);
+        register_scene_setup::<Marker, _, _>(self, system_id);
+        add_scene_input_cleanup::<_, T>(self, &state);
+        let entered = state.clone();
+        self.add_systems(OnEnter(state.clone()), move |world: &mut World| {
+            run_setup_with_input::<_, T>(world, Marker::type_path(), &entered);
+            restore_scene::<Marker>(world);
+            enter_scene::<Marker>(world);
+        });
+        self.add_systems(OnExit(state), cleanup_scene::<Marker>);
+    }
+
+    fn add_sliced_scene<Marker: Component + TypePath, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<SceneSlice, SceneBuildProgress, M> + 'static,
+        budget: usize,
+        state: impl States,
+    ) {
+        let system_id = self.world_mut().register_system(setup_system);
+        register_scene_setup::<Marker, _, _>(self, system_id);
+        if !self.world().contains_resource::<SceneBuilds>() {
+            self.init_resource::<SceneBuilds>()
+                .add_event::<SceneReady>()
+                .add_systems(Update, build_scene_slices);
+        }
+        self.add_systems(OnEnter(state.clone()), move |world: &mut World| {
+            start_scene_build::<Marker>(world, budget.max(1));
+        });
+        self.add_systems(
+            OnExit(state),
+            (cancel_scene_build::<Marker>, cleanup_scene::<Marker>),
+        );
+    }
+
+    fn add_scene_in_states<Marker: Component + TypePath, M, S: States>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<(), (), M> + 'static,
+        states: impl IntoIterator<Item = S>,
+    ) {
+        let states: Vec<S> = states.into_iter().collect();
+        self.add_scene_when(marker_component, setup_system, move |state: &S| {
+            states.contains(state)
+        });
+    }
+
+    fn add_scene_when<Marker: Component + TypePath, M, S: States>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<(), (), M> + 'static,
+        predicate: impl Fn(&S) -> bool + Clone + Send + Sync + 'static,
+    ) {
+        let system_id = self.world_mut().register_system(setup_system);
+        register_scene_setup::<Marker, _, _>(self, system_id);
+
+        // Make sure the transition schedule is set up properly before we add to it, otherwise adding our systems would create
+        // a bare schedule that bevy then refuses to configure
+        setup_state_transitions_in_world(self.world_mut());
+
+        let entered = predicate.clone();
+        self.add_systems(
+            StateTransition,
+            setup_scene::<Marker>
+                .run_if(move |transitions: EventReader<StateTransitionEvent<S>>| {
+                    transition_matches(transitions, &entered, true)
+                })
+                .in_set(EnterSchedules::<S>::default()),
+        );
+        self.add_systems(
+            StateTransition,
+            cleanup_scene::<Marker>
+                .run_if(move |transitions: EventReader<StateTransitionEvent<S>>| {
+                    transition_matches(transitions, &predicate, false)
+                })
+                .in_set(ExitSchedules::<S>::default()),
+        );
+    }
+
+    fn add_overlay_scene<Marker: Component + TypePath, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<(), (), M> + 'static,
+    ) {
+        let system_id = self.world_mut().register_system(setup_system);
+        register_scene_setup::<Marker, _, _>(self, system_id);
+        self.init_resource::<SceneStack>();
+    }
+
+    fn remember_scene_state<Marker: Component + TypePath>(&mut self, _: MarkerComponent<Marker>) {
+        self.world_mut()
+            .get_resource_or_insert_with(SceneMemory::default)
+            .remember::<Marker>();
+    }
+
+    fn set_scene_transitions<Marker: Component + TypePath>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        transitions: SceneTransitions,
+    ) {
+        if !self.world().contains_resource::<SceneTransitionSettings>() {
+            self.init_resource::<SceneTransitionSettings>()
+                .add_systems(Update, animate_scene_transitions);
+        }
+        self.world_mut()
+            .resource_mut::<SceneTransitionSettings>()
+            .map
+            .insert(Marker::type_path(), transitions);
+    }
+
+    fn add_scene_teardown<Marker: Component + TypePath, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        teardown_system: impl IntoSystem<(), (), M> + 'static,
+    ) {
+        let system_id = self.world_mut().register_system(teardown_system);
+        insert_system_id(self, Marker::type_path(), SCENE_TEARDOWN_LABEL, system_id);
+    }
+}
+
+pub trait ScenesWorldExt {
+    /// Replaces the setup system of the scene marked with the given marker component, removing the old one from the world.
+    ///
+    /// If `rebuild` is true and the scene is currently spawned it is torn down and set up again with the new system straight
+    /// away, otherwise the new system is used the next time the scene is entered. Scenes on the [`SceneStack`] are not
+    /// rebuilt, pop and push them again instead.
+    ///
+    /// Only scenes with a plain setup system can be replaced. Replacing the setup system of a scene added with
+    /// [`add_scene_with_input`](ScenesAppExtension::add_scene_with_input) or
+    /// [`add_sliced_scene`](ScenesAppExtension::add_sliced_scene) leaves it untouched and returns a
+    /// [`OneShotSystemError::MismatchedTypes`], which is also logged and sent as an event.
+    fn replace_scene_setup<Marker: Component + TypePath, M>(
+        &mut self,
+        marker_component: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<(), (), M> + 'static,
+        rebuild: bool,
+    ) -> Result<(), OneShotSystemError>;
+}
+
+impl ScenesWorldExt for World {
+    fn replace_scene_setup<Marker: Component + TypePath, M>(
+        &mut self,
+        _: MarkerComponent<Marker>,
+        setup_system: impl IntoSystem<(), (), M> + 'static,
+        rebuild: bool,
+    ) -> Result<(), OneShotSystemError> {
+        // The scene runs its setup system with the input and output it was added with, so it can't be swapped for another
+        let existing = self
+            .get_resource::<OneShotSystemIds>()
+            .map(|ids| ids.get(Marker::type_path(), SCENE_SETUP_LABEL));
+        if let Some(Err(error @ OneShotSystemError::MismatchedTypes { .. })) = existing {
+            report_error(self, error);
+            return Err(error);
+        }
+
+        self.replace_marker_system::<Marker, (), (), M>(SCENE_SETUP_LABEL, setup_system);
+        let on_stack = self
+            .get_resource::<SceneStack>()
+            .is_some_and(|stack| stack.contains::<Marker>());
+        if rebuild && !on_stack {
+            rebuild_scene::<Marker>(self);
+        }
+        Ok(())
+    }
+}
+
+/// Registers the setup system of the scene and remembers how to find its roots
+fn register_scene_setup<Marker: Component + TypePath, I: 'static, O: 'static>(
+    app: &mut App,
+    system_id: SystemId<I, O>,
+) {
+    insert_system_id(app, Marker::type_path(), SCENE_SETUP_LABEL, system_id);
+    app.world_mut()
+        .get_resource_or_insert_with(SceneRootQueries::default)
+        .map
+        .insert(Marker::type_path(), scene_roots::<Marker>);
+}
+
+/// The top most entities of the scene, every entity with the marker whose parent doesn't have it
+pub(crate) fn scene_roots<Marker: Component>(world: &mut World) -> Vec<Entity> {
+    let mut query = world.query_filtered::<(Entity, Option<&Parent>), With<Marker>>();
+    query
+        .iter(world)
+        .filter(|(_, parent)| {
+            parent.is_none_or(|parent| world.get::<Marker>(parent.get()).is_none())
+        })
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+/// The given roots and all of their descendants in hierarchy order. Entities that no longer exist are skipped.
+pub(crate) fn scene_hierarchy(
+    world: &World,
+    roots: impl IntoIterator<Item = Entity>,
+) -> Vec<Entity> {
+    let mut stack: Vec<Entity> = roots.into_iter().collect();
+    stack.reverse();
+
+    let mut visited = HashSet::new();
+    let mut entities = vec![];
+    while let Some(entity) = stack.pop() {
+        if world.get_entity(entity).is_none() || !visited.insert(entity) {
+            continue;
+        }
+        entities.push(entity);
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter().rev());
+        }
+    }
+    entities
+}
+
+/// Clears input of type `T` supplied for states of type `S` after every transition, once the entered scenes had the chance to
+/// use it
+fn add_scene_input_cleanup<S: States, T: Send + Sync + 'static>(app: &mut App, _: &S) {
+    setup_state_transitions_in_world(app.world_mut());
+    app.add_systems(
+        StateTransition,
+        clear_unused_scene_input::<S, T>.after(EnterSchedules::<S>::default()),
+    );
+}
+
+/// Tears down the scene if it is spawned and sets it up again without animating it in or out
+fn rebuild_scene<Marker: Component + TypePath>(world: &mut World) {
+    let mut query = world.query_filtered::<Entity, With<Marker>>();
+    let entities: Vec<Entity> = query.iter(world).collect();
+    if entities.is_empty() {
+        return;
+    }
+
+    let Some(ids) = world.get_resource::<OneShotSystemIds>() else {
+        return;
+    };
+    let teardown = ids.get(Marker::type_path(), SCENE_TEARDOWN_LABEL).ok();
+    let setup = ids.get(Marker::type_path(), SCENE_SETUP_LABEL);
+
+    if let Some(teardown) = teardown {
+        if let Err(error) = world.run_system(teardown) {
+            warn!("Failed to tear down scene {}: {error}", Marker::type_path());
+        }
+    }
+    snapshot_scene::<Marker>(world);
+    for entity in entities {
+        if let Some(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+
+    match setup {
+        Ok(setup) => {
+            if let Err(error) = world.run_system(setup) {
+                warn!("Failed to set up scene {}: {error}", Marker::type_path());
+            }
+            restore_scene::<Marker>(world);
+        }
+        Err(error) => report_error(world, error),
+    }
+}
+
+/// Looks up the setup system of the scene every time it is needed, so that a replaced setup system is picked up. Errors are
+/// logged and sent as events.
+pub(crate) fn scene_setup_system<I: 'static, O: 'static>(
+    world: &mut World,
+    scene: &'static str,
+) -> Option<SystemId<I, O>> {
+    let system_id = world
+        .get_resource::<OneShotSystemIds>()
+        .ok_or(OneShotSystemError::NotRegistered {
+            marker: scene,
+            label: SCENE_SETUP_LABEL,
+        })
+        .and_then(|ids| ids.get_typed::<I, O>(scene, SCENE_SETUP_LABEL));
+    match system_id {
+        Ok(system_id) => Some(system_id),
+        Err(error) => {
+            report_error(world, error);
+            None
+        }
+    }
+}
+
+/// Returns true if the last transition of `S` entered the set of states matching the predicate, or left it if `entering` is false
+fn transition_matches<S: States>(
+    mut transitions: EventReader<StateTransitionEvent<S>>,
+    predicate: &impl Fn(&S) -> bool,
+    entering: bool,
+) -> bool {
+    let Some(transition) = transitions.read().last() else {
+        return false;
+    };
+    let exited = transition.exited.as_ref().is_some_and(predicate);
+    let entered = transition.entered.as_ref().is_some_and(predicate);
+    match entering {
+        true => entered && !exited,
+        false => exited && !entered,
+    }
 }
 
 fn setup_scene<SceneRootMarker: Component + TypePath>(
     mut commands: Commands,
     resource: Res<OneShotSystemIds>,
+    mut errors: EventWriter<OneShotSystemError>,
 ) {
-    let system_id = resource.map.get(SceneRootMarker::type_path()).unwrap();
-    commands.run_system(*system_id);
+    match resource.get(SceneRootMarker::type_path(), SCENE_SETUP_LABEL) {
+        Ok(system_id) => {
+            commands.run_system(system_id);
+            commands.add(restore_scene::<SceneRootMarker>);
+            commands.add(enter_scene::<SceneRootMarker>);
+        }
+        Err(error) => {
+            warn!("Failed to set up scene: {error}");
+            errors.send(error);
+        }
+    }
 }
 
-fn cleanup_scene<SceneRootMarker: Component>(
-    query: Query<Entity, With<SceneRootMarker>>,
+fn cleanup_scene<SceneRootMarker: Component + TypePath>(
+    resource: Res<OneShotSystemIds>,
     mut commands: Commands,
 ) {
-    for entity in query.iter() {
-        commands.entity(entity).despawn_recursive();
+    // Commands are applied in order so the teardown system runs while the scene is still alive
+    if let Ok(system_id) = resource.get(SceneRootMarker::type_path(), SCENE_TEARDOWN_LABEL) {
+        commands.run_system(system_id);
     }
+    commands.add(snapshot_scene::<SceneRootMarker>);
+    commands.add(exit_scene::<SceneRootMarker>);
 }
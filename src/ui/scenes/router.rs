@@ -0,0 +1,129 @@
+//! A router that navigates between scenes while keeping a history of where it has been.
+//!
+//! Each route is a value of a state `S` that has scenes added to it with [`ScenesAppExtension`](super::ScenesAppExtension).
+//! Navigation goes through the [`SceneNavigator`] system param, which changes the state and records the history so that
+//! [`SceneNavigator::back`] can return to the previous scene.
+
+use bevy::{
+    app::{App, Update},
+    ecs::{
+        query::With,
+        system::{Query, Res, ResMut, Resource, SystemParam},
+    },
+    prelude::{NextState, State},
+    state::state::FreelyMutableState,
+};
+use leafwing_input_manager::{action_state::ActionState, Actionlike};
+
+use crate::controls::ClientController;
+
+/// The history of scenes visited through the [`SceneNavigator`]. Does not include the current scene.
+#[derive(Resource)]
+pub struct SceneRouter<S: FreelyMutableState> {
+    history: Vec<S>,
+}
+
+impl<S: FreelyMutableState> Default for SceneRouter<S> {
+    fn default() -> Self {
+        Self { history: vec![] }
+    }
+}
+
+impl<S: FreelyMutableState> SceneRouter<S> {
+    /// Every scene visited before the current one, oldest first
+    pub fn history(&self) -> &[S] {
+        &self.history
+    }
+
+    /// True if there is a previous scene to go back to
+    pub fn can_go_back(&self) -> bool {
+        !self.history.is_empty()
+    }
+}
+
+/// Navigates between scenes, recording the history in the [`SceneRouter`]
+#[derive(SystemParam)]
+pub struct SceneNavigator<'w, S: FreelyMutableState> {
+    router: ResMut<'w, SceneRouter<S>>,
+    state: Res<'w, State<S>>,
+    next_state: ResMut<'w, NextState<S>>,
+}
+
+impl<'w, S: FreelyMutableState> SceneNavigator<'w, S> {
+    /// Goes to the given scene, remembering the current one so it can be gone back to.
+    ///
+    /// If another navigation is still pending, the scene it is going to counts as the current one. Navigating twice in one
+    /// frame records both steps, so going back from the second scene returns to the first.
+    pub fn navigate(&mut self, scene: S) {
+        let current = self.current().clone();
+        if current == scene {
+            return;
+        }
+        self.router.history.push(current);
+        self.next_state.set(scene);
+    }
+
+    /// Goes back to the previous scene. Returns false if there was nothing to go back to
+    pub fn back(&mut self) -> bool {
+        let Some(previous) = self.router.history.pop() else {
+            return false;
+        };
+        self.next_state.set(previous);
+        true
+    }
+
+    /// Goes to the given scene and forgets the entire history
+    pub fn reset_to(&mut self, scene: S) {
+        self.router.history.clear();
+        self.next_state.set(scene);
+    }
+
+    /// The history of the router
+    pub fn router(&self) -> &SceneRouter<S> {
+        &self.router
+    }
+
+    /// The scene that is shown, or the one that is about to be if a navigation is pending
+    fn current(&self) -> &S {
+        match self.next_state.as_ref() {
+            NextState::Pending(next) => next,
+            NextState::Unchanged => self.state.get(),
+        }
+    }
+}
+
+pub trait SceneRouterAppExtension {
+    /// Adds a [`SceneRouter`] for the given state so that it can be navigated with a [`SceneNavigator`]
+    fn add_scene_router<S: FreelyMutableState>(&mut self);
+
+    /// Goes back to the previous scene whenever the given action is just pressed.
+    ///
+    /// The action should be set up with [`ActionBusyworkTrait::setup_action`](crate::controls::ActionBusyworkTrait::setup_action).
+    /// It is read from either the [`ActionState`] resource or the [`ClientController`] entity.
+    fn bind_router_back<S: FreelyMutableState, A: Actionlike>(&mut self, action: A);
+}
+
+impl SceneRouterAppExtension for App {
+    fn add_scene_router<S: FreelyMutableState>(&mut self) {
+        self.init_resource::<SceneRouter<S>>();
+    }
+
+    fn bind_router_back<S: FreelyMutableState, A: Actionlike>(&mut self, action: A) {
+        self.add_scene_router::<S>();
+        self.add_systems(
+            Update,
+            move |resource_action_state: Option<Res<ActionState<A>>>,
+                  controller_action_state: Query<&ActionState<A>, With<ClientController>>,
+                  mut navigator: SceneNavigator<S>| {
+                let pressed = resource_action_state
+                    .iter()
+                    .map(|action_state| action_state.as_ref())
+                    .chain(controller_action_state.iter())
+                    .any(|action_state| action_state.just_pressed(&action));
+                if pressed {
+                    navigator.back();
+                }
+            },
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Scenes that are built over multiple frames.
+//!
+//! Large scenes can take long enough to spawn that doing it in a single frame causes a visible hitch. A sliced scene instead
+//! runs its setup system once per frame, handing it a [`SceneSlice`] describing which part of the scene to build, until the
+//! setup system reports that it is [`SceneBuildProgress::Complete`]. The scene is kept hidden while it is being built and a
+//! [`SceneReady`] event is sent once it is done.
+
+use std::ops::Range;
+
+use bevy::{
+    ecs::{component::Component, event::Event, query::With, system::Resource, world::World},
+    log::warn,
+    reflect::TypePath,
+    render::view::Visibility,
+};
+
+use super::{memory::restore_scene, scene_setup_system, transitions::enter_scene};
+
+/// The part of a sliced scene that its setup system should build this frame
+#[derive(Clone, Copy, Debug)]
+pub struct SceneSlice {
+    /// How many frames the scene has been building for, starting at 0
+    pub frame: u32,
+    /// Index of the first item to build this frame
+    pub start: usize,
+    /// The maximum number of items to build this frame
+    pub budget: usize,
+}
+
+impl SceneSlice {
+    /// The range of items to build this frame
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.start + self.budget
+    }
+}
+
+/// Returned by a sliced setup system to report whether the scene is done building
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SceneBuildProgress {
+    /// There is more to build, the setup system will run again next frame with the next slice
+    Building,
+    /// The scene is fully built and can be shown
+    Complete,
+}
+
+/// Sent when a sliced scene has finished building and has been made visible
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SceneReady {
+    /// The [`TypePath`] of the scenes marker component
+    pub scene: &'static str,
+}
+
+impl SceneReady {
+    /// Returns true if this event is for the scene marked with the given marker component
+    pub fn is<Marker: Component + TypePath>(&self) -> bool {
+        self.scene == Marker::type_path()
+    }
+}
+
+/// Sliced scenes that are currently being built
+#[derive(Resource, Default)]
+pub(crate) struct SceneBuilds {
+    active: Vec<SceneBuild>,
+}
+
+struct SceneBuild {
+    scene: &'static str,
+    frame: u32,
+    budget: usize,
+    set_visibility: fn(&mut World, Visibility),
+    restore: fn(&mut World),
+    enter: fn(&mut World),
+}
+
+/// Starts building the sliced scene, it will be built a slice at a time by [`build_scene_slices`]
+pub(crate) fn start_scene_build<Marker: Component + TypePath>(world: &mut World, budget: usize) {
+    if scene_setup_system::<SceneSlice, SceneBuildProgress>(world, Marker::type_path()).is_none() {
+        return;
+    }
+    let mut builds = world.resource_mut::<SceneBuilds>();
+    builds
+        .active
+        .retain(|build| build.scene != Marker::type_path());
+    builds.active.push(SceneBuild {
+        scene: Marker::type_path(),
+        frame: 0,
+        budget,
+        set_visibility: set_scene_visibility::<Marker>,
+        restore: restore_scene::<Marker>,
+        enter: enter_scene::<Marker>,
+    });
+}
+
+/// Stops building the sliced scene if it is still being built
+pub(crate) fn cancel_scene_build<Marker: Component + TypePath>(world: &mut World) {
+    world
+        .resource_mut::<SceneBuilds>()
+        .active
+        .retain(|build| build.scene != Marker::type_path());
+}
+
+/// Builds the next slice of every sliced scene that is still being built
+pub(crate) fn build_scene_slices(world: &mut World) {
+    let builds = std::mem::take(&mut world.resource_mut::<SceneBuilds>().active);
+    let mut still_building = vec![];
+
+    for mut build in builds {
+        let slice = SceneSlice {
+            frame: build.frame,
+            start: build.frame as usize * build.budget,
+            budget: build.budget,
+        };
+        // The setup system is looked up every slice, in case it was replaced while the scene was being built
+        let Some(system_id) = scene_setup_system(world, build.scene) else {
+            continue;
+        };
+        let progress = match world.run_system_with_input(system_id, slice) {
+            Ok(progress) => progress,
+            Err(error) => {
+                warn!("Failed to build scene {}: {error}", build.scene);
+                continue;
+            }
+        };
+
+        match progress {
+            SceneBuildProgress::Building => {
+                (build.set_visibility)(world, Visibility::Hidden);
+                build.frame += 1;
+                still_building.push(build);
+            }
+            SceneBuildProgress::Complete => {
+                (build.restore)(world);
+                (build.set_visibility)(world, Visibility::Inherited);
+                (build.enter)(world);
+                world.send_event(SceneReady { scene: build.scene });
+            }
+        }
+    }
+
+    // Scenes may have started building while we were running the setup systems, keep those too
+    world
+        .resource_mut::<SceneBuilds>()
+        .active
+        .extend(still_building);
+}
+
+fn set_scene_visibility<Marker: Component>(world: &mut World, visibility: Visibility) {
+    let mut query = world.query_filtered::<&mut Visibility, With<Marker>>();
+    for mut scene_visibility in query.iter_mut(world) {
+        *scene_visibility = visibility;
+    }
+}
@@ -0,0 +1,262 @@
+//! A stack of overlay scenes that are independent of [`States`](bevy::prelude::States).
+//!
+//! Overlay scenes are built with the same marker component + setup system pattern as normal scenes but instead of being tied
+//! to a state they are pushed on top of whatever ui is currently displayed. Popping an overlay despawns it and restores the
+//! scenes below it to how they were before it was pushed.
+
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        system::{Commands, Resource},
+        world::World,
+    },
+    hierarchy::DespawnRecursiveExt,
+    log::warn,
+    prelude::default,
+    reflect::TypePath,
+    render::view::Visibility,
+    ui::{node_bundles::NodeBundle, FocusPolicy, PositionType, Style, Val, ZIndex},
+};
+
+use super::{
+    memory::{restore_scene, snapshot_scene},
+    scene_roots,
+    transitions::{enter_scene, exit_scene},
+    SceneRootQueries, SCENE_SETUP_LABEL, SCENE_TEARDOWN_LABEL,
+};
+use crate::{
+    one_shot_system::{report_error, OneShotSystemIds},
+    ui::layers::{UiLayer, UiLayers},
+};
+
+/// What should happen to the scenes below an overlay scene while the overlay is on top of them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LowerSceneBehavior {
+    /// Lower scenes stay visible and interactive
+    #[default]
+    Keep,
+    /// Every other scene, both the overlay scenes lower in the stack and the scenes of the current state, is hidden until the
+    /// overlay is popped. Ui that isn't part of a scene is left alone.
+    Hide,
+    /// Lower scenes stay visible but a transparent node is placed between them and the overlay that blocks all interaction
+    BlockInput,
+}
+
+/// The stack of currently active overlay scenes. The last entry is the top of the stack.
+#[derive(Resource, Default)]
+pub struct SceneStack {
+    entries: Vec<SceneStackEntry>,
+}
+
+impl SceneStack {
+    /// The number of overlay scenes currently on the stack
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True if there are no overlay scenes on the stack
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The [`TypePath`] of the marker component of the overlay scene currently on top of the stack
+    pub fn top(&self) -> Option<&'static str> {
+        self.entries.last().map(|entry| entry.scene)
+    }
+
+    /// Returns true if the overlay scene marked with the given marker component is anywhere in the stack
+    pub fn contains<Marker: Component + TypePath>(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.scene == Marker::type_path())
+    }
+}
+
+struct SceneStackEntry {
+    scene: &'static str,
+    /// Entity blocking input to the scenes below this one
+    blocker: Option<Entity>,
+    /// Root ui nodes that were hidden when this scene was pushed and the visibility they had before
+    hidden: Vec<(Entity, Visibility)>,
+    snapshot: fn(&mut World),
+    exit: fn(&mut World),
+}
+
+/// Commands to manipulate the [`SceneStack`]
+pub trait SceneStackCommandsExt {
+    /// Pushes the overlay scene marked with the given marker component on top of the stack and runs its setup system.
+    ///
+    /// An overlay scene can only be on the stack once, pushing one that is already on it logs a warning and does nothing.
+    fn push_scene<Marker: Component + TypePath>(&mut self, lower_scenes: LowerSceneBehavior);
+
+    /// Pops the overlay scene on top of the stack, despawning it and restoring the scene below it.
+    fn pop_scene(&mut self);
+
+    /// Pops the overlay scene on top of the stack and pushes the given one in its place.
+    fn replace_scene<Marker: Component + TypePath>(&mut self, lower_scenes: LowerSceneBehavior);
+}
+
+impl SceneStackCommandsExt for Commands<'_, '_> {
+    fn push_scene<Marker: Component + TypePath>(&mut self, lower_scenes: LowerSceneBehavior) {
+        self.add(move |world: &mut World| push_scene::<Marker>(world, lower_scenes));
+    }
+
+    fn pop_scene(&mut self) {
+        self.add(pop_scene);
+    }
+
+    fn replace_scene<Marker: Component + TypePath>(&mut self, lower_scenes: LowerSceneBehavior) {
+        self.add(move |world: &mut World| {
+            pop_scene(world);
+            push_scene::<Marker>(world, lower_scenes);
+        });
+    }
+}
+
+fn push_scene<Marker: Component + TypePath>(world: &mut World, lower_scenes: LowerSceneBehavior) {
+    // Both instances would share the marker component, so popping either of them would despawn the other as well
+    if world
+        .get_resource::<SceneStack>()
+        .is_some_and(|stack| stack.contains::<Marker>())
+    {
+        warn!(
+            "Overlay scene {} is already on the scene stack",
+            Marker::type_path()
+        );
+        return;
+    }
+
+    let system_id = match world
+        .get_resource_or_insert_with(OneShotSystemIds::default)
+        .get(Marker::type_path(), SCENE_SETUP_LABEL)
+    {
+        Ok(system_id) => system_id,
+        Err(error) => {
+            report_error(world, error);
+            return;
+        }
+    };
+
+    let depth = world.get_resource_or_insert_with(SceneStack::default).len() as i32;
+    // Overlays are drawn on the menu layer, every scene in the stack gets two z levels within it, one for its input blocker
+    // and one for the scene itself
+    let blocker_z = world
+        .get_resource_or_insert_with(UiLayers::default)
+        .global_z(UiLayer::Menu)
+        + 1
+        + depth * 2;
+
+    let mut hidden = vec![];
+    if lower_scenes == LowerSceneBehavior::Hide {
+        // Every scene registers a setup system, which covers both the overlays lower in the stack and the scenes of the
+        // current state. Scenes that aren't spawned simply have no roots.
+        let lower_roots: Vec<_> = match (
+            world.get_resource::<OneShotSystemIds>(),
+            world.get_resource::<SceneRootQueries>(),
+        ) {
+            (Some(ids), Some(queries)) => ids
+                .registered()
+                .filter(|info| {
+                    info.key.label == SCENE_SETUP_LABEL && info.key.marker != Marker::type_path()
+                })
+                .filter_map(|info| queries.map.get(info.key.marker).copied())
+                .collect(),
+            _ => vec![],
+        };
+        for roots in lower_roots {
+            for entity in roots(world) {
+                let Some(mut visibility) = world.get_mut::<Visibility>(entity) else {
+                    continue;
+                };
+                if *visibility != Visibility::Hidden {
+                    hidden.push((entity, *visibility));
+                    *visibility = Visibility::Hidden;
+                }
+            }
+        }
+    }
+
+    let blocker = (lower_scenes == LowerSceneBehavior::BlockInput).then(|| {
+        world
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(blocker_z),
+                ..default()
+            })
+            .id()
+    });
+
+    if let Err(error) = world.run_system(system_id) {
+        warn!(
+            "Failed to run setup for overlay scene {}: {error}",
+            Marker::type_path()
+        );
+    }
+
+    restore_scene::<Marker>(world);
+    enter_scene::<Marker>(world);
+
+    for root in scene_roots::<Marker>(world) {
+        // Roots that picked their own layer are left on it
+        if world.get::<UiLayer>(root).is_some() {
+            continue;
+        }
+        if let Some(mut z_index) = world.get_mut::<ZIndex>(root) {
+            *z_index = ZIndex::Global(blocker_z + 1);
+        }
+    }
+
+    world
+        .resource_mut::<SceneStack>()
+        .entries
+        .push(SceneStackEntry {
+            scene: Marker::type_path(),
+            blocker,
+            hidden,
+            snapshot: snapshot_scene::<Marker>,
+            exit: exit_scene::<Marker>,
+        });
+}
+
+fn pop_scene(world: &mut World) {
+    let Some(entry) = world
+        .get_resource_mut::<SceneStack>()
+        .and_then(|mut stack| stack.entries.pop())
+    else {
+        return;
+    };
+
+    if let Some(system_id) = world
+        .get_resource::<OneShotSystemIds>()
+        .and_then(|resource| resource.get(entry.scene, SCENE_TEARDOWN_LABEL).ok())
+    {
+        if let Err(error) = world.run_system(system_id) {
+            warn!(
+                "Failed to run teardown for overlay scene {}: {error}",
+                entry.scene
+            );
+        }
+    }
+
+    (entry.snapshot)(world);
+    (entry.exit)(world);
+
+    if let Some(blocker) = entry.blocker {
+        if let Some(blocker) = world.get_entity_mut(blocker) {
+            blocker.despawn_recursive();
+        }
+    }
+
+    for (entity, visibility) in entry.hidden {
+        if let Some(mut current_visibility) = world.get_mut::<Visibility>(entity) {
+            *current_visibility = visibility;
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Animated enter and exit transitions for scenes.
+//!
+//! By default scenes are spawned and despawned instantly. Scenes that have [`SceneTransitions`] set animate their root
+//! entities in after they are set up, and when they are left their roots are kept alive until the exit animation has finished.
+//! Leaving roots have their marker component removed so that they can't be confused with the next instance of the scene.
+
+use std::time::Duration;
+
+use bevy::{
+    color::Alpha,
+    ecs::{component::Component, entity::Entity, query::With, system::Resource, world::World},
+    hierarchy::DespawnRecursiveExt,
+    math::Vec3,
+    reflect::TypePath,
+    render::view::Visibility,
+    text::Text,
+    time::Time,
+    transform::components::Transform,
+    ui::{BackgroundColor, BorderColor, Style, Val},
+    utils::HashMap,
+};
+
+use super::{scene_hierarchy, scene_roots};
+
+/// How a scene animates when it is entered or left
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SceneTransition {
+    /// No animation, the scene appears or disappears instantly
+    #[default]
+    None,
+    /// Fades the whole scene in or out
+    Fade,
+    /// Slides the scene in from, or out to, the given edge of the screen
+    Slide(SlideEdge),
+    /// Scales the scene up from nothing, or down to nothing
+    Scale,
+}
+
+/// An edge of the screen that a scene can slide in from or out to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlideEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// The enter and exit transitions of a scene
+#[derive(Clone, Copy, Debug)]
+pub struct SceneTransitions {
+    pub enter: SceneTransition,
+    pub exit: SceneTransition,
+    /// How long each transition takes
+    pub duration: Duration,
+}
+
+impl Default for SceneTransitions {
+    fn default() -> Self {
+        Self {
+            enter: SceneTransition::None,
+            exit: SceneTransition::None,
+            duration: Duration::from_millis(300),
+        }
+    }
+}
+
+/// The transitions of every scene that has them, keyed by the scene marker components [`TypePath`]
+#[derive(Resource, Default)]
+pub(crate) struct SceneTransitionSettings {
+    pub(crate) map: HashMap<&'static str, SceneTransitions>,
+}
+
+/// A scene root that is currently animating in or out
+#[derive(Component)]
+pub struct SceneAnimation {
+    transition: SceneTransition,
+    exiting: bool,
+    elapsed: Duration,
+    duration: Duration,
+    /// The position the root had before the animation started, restored once it has entered
+    original_offset: (Val, Val),
+}
+
+impl SceneAnimation {
+    /// True if the scene is animating out and will be despawned when the animation finishes
+    pub fn is_exiting(&self) -> bool {
+        self.exiting
+    }
+}
+
+/// The alpha every color of a fading entity had before the fade started
+#[derive(Component)]
+struct FadeBaseAlpha {
+    background: Option<f32>,
+    border: Option<f32>,
+    text: Vec<f32>,
+}
+
+fn transitions_for<Marker: Component + TypePath>(world: &World) -> Option<SceneTransitions> {
+    world
+        .get_resource::<SceneTransitionSettings>()
+        .and_then(|settings| settings.map.get(Marker::type_path()).copied())
+}
+
+/// Starts the enter animation of the scene if it has one
+pub(crate) fn enter_scene<Marker: Component + TypePath>(world: &mut World) {
+    let Some(transitions) = transitions_for::<Marker>(world) else {
+        return;
+    };
+    if transitions.enter == SceneTransition::None {
+        return;
+    }
+
+    for root in scene_roots::<Marker>(world) {
+        start_animation(world, root, transitions.enter, false, transitions.duration);
+    }
+}
+
+/// Despawns the scene, or starts its exit animation and leaves it to be despawned once that finishes
+pub(crate) fn exit_scene<Marker: Component + TypePath>(world: &mut World) {
+    let transition = transitions_for::<Marker>(world)
+        .filter(|transitions| transitions.exit != SceneTransition::None);
+
+    let roots = scene_roots::<Marker>(world);
+    let mut query = world.query_filtered::<Entity, With<Marker>>();
+    let entities: Vec<Entity> = query.iter(world).collect();
+
+    let Some(transitions) = transition else {
+        for entity in entities {
+            if let Some(entity) = world.get_entity_mut(entity) {
+                entity.despawn_recursive();
+            }
+        }
+        return;
+    };
+
+    for entity in entities {
+        world.entity_mut(entity).remove::<Marker>();
+    }
+    for root in roots {
+        start_animation(world, root, transitions.exit, true, transitions.duration);
+    }
+}
+
+fn start_animation(
+    world: &mut World,
+    root: Entity,
+    transition: SceneTransition,
+    exiting: bool,
+    duration: Duration,
+) {
+    // A scene can be left while it is still animating in, in which case the exit picks up from the original position
+    let original_offset = match world.get::<SceneAnimation>(root) {
+        Some(animation) => animation.original_offset,
+        None => world
+            .get::<Style>(root)
+            .map(|style| (style.left, style.top))
+            .unwrap_or((Val::Auto, Val::Auto)),
+    };
+
+    let Some(mut entity) = world.get_entity_mut(root) else {
+        return;
+    };
+    entity.insert(SceneAnimation {
+        transition,
+        exiting,
+        elapsed: Duration::ZERO,
+        duration,
+        original_offset,
+    });
+    apply_animation(world, root, 0.0);
+}
+
+/// Advances every scene animation, despawning scenes that finished animating out
+pub(crate) fn animate_scene_transitions(world: &mut World) {
+    let delta = world.resource::<Time>().delta();
+
+    let mut query = world.query::<(Entity, &mut SceneAnimation, Option<&Visibility>)>();
+    let mut progressed = vec![];
+    for (entity, mut animation, visibility) in query.iter_mut(world) {
+        // Scenes that are still hidden, such as sliced scenes being built, shouldn't animate until they can be seen
+        if visibility == Some(&Visibility::Hidden) && !animation.exiting {
+            continue;
+        }
+        animation.elapsed += delta;
+        let progress = match animation.duration.is_zero() {
+            true => 1.0,
+            false => (animation.elapsed.as_secs_f32() / animation.duration.as_secs_f32()).min(1.0),
+        };
+        progressed.push((entity, progress, animation.exiting));
+    }
+
+    for (entity, progress, exiting) in progressed {
+        // Finishing an earlier scene can despawn this one with it, if it was nested inside of that scene
+        if world.get_entity(entity).is_none() {
+            continue;
+        }
+        apply_animation(world, entity, progress);
+        if progress < 1.0 {
+            continue;
+        }
+        if exiting {
+            if let Some(entity) = world.get_entity_mut(entity) {
+                entity.despawn_recursive();
+            }
+        } else {
+            finish_enter(world, entity);
+        }
+    }
+}
+
+/// Sets the scene to how it should look at the given progress through its animation
+fn apply_animation(world: &mut World, root: Entity, progress: f32) {
+    let Some(animation) = world.get::<SceneAnimation>(root) else {
+        return;
+    };
+    // Smoothstep so the animation eases in and out
+    let eased = progress * progress * (3.0 - 2.0 * progress);
+    // How much of the scene is shown, 0 is fully gone and 1 is fully in place
+    let shown = match animation.exiting {
+        true => 1.0 - eased,
+        false => eased,
+    };
+    let (original_left, original_top) = animation.original_offset;
+
+    match animation.transition {
+        SceneTransition::None => {}
+        SceneTransition::Fade => fade(world, root, shown),
+        SceneTransition::Slide(edge) => {
+            let Some(mut style) = world.get_mut::<Style>(root) else {
+                return;
+            };
+            let hidden = 100.0 * (1.0 - shown);
+            let (left, top) = match edge {
+                SlideEdge::Left => (Val::Percent(-hidden), original_top),
+                SlideEdge::Right => (Val::Percent(hidden), original_top),
+                SlideEdge::Top => (original_left, Val::Percent(-hidden)),
+                SlideEdge::Bottom => (original_left, Val::Percent(hidden)),
+            };
+            style.left = left;
+            style.top = top;
+        }
+        SceneTransition::Scale => {
+            if let Some(mut transform) = world.get_mut::<Transform>(root) {
+                transform.scale = Vec3::splat(shown.max(f32::EPSILON));
+            }
+        }
+    }
+}
+
+/// Puts the scene back exactly how its setup system built it once it has finished animating in
+fn finish_enter(world: &mut World, root: Entity) {
+    let Some(animation) = world
+        .get_entity_mut(root)
+        .and_then(|mut root| root.take::<SceneAnimation>())
+    else {
+        return;
+    };
+    match animation.transition {
+        SceneTransition::None | SceneTransition::Fade => {}
+        SceneTransition::Slide(_) => {
+            if let Some(mut style) = world.get_mut::<Style>(root) {
+                (style.left, style.top) = animation.original_offset;
+            }
+        }
+        SceneTransition::Scale => {
+            if let Some(mut transform) = world.get_mut::<Transform>(root) {
+                transform.scale = Vec3::ONE;
+            }
+        }
+    }
+
+    for entity in scene_hierarchy(world, [root]) {
+        if let Some(base) = world.entity_mut(entity).take::<FadeBaseAlpha>() {
+            set_alpha(world, entity, &base, 1.0);
+        }
+    }
+}
+
+/// Multiplies the alpha of every color in the scene by the given opacity
+fn fade(world: &mut World, root: Entity, opacity: f32) {
+    for entity in scene_hierarchy(world, [root]) {
+        if world.get::<FadeBaseAlpha>(entity).is_none() {
+            let base = FadeBaseAlpha {
+                background: world
+                    .get::<BackgroundColor>(entity)
+                    .map(|color| color.0.alpha()),
+                border: world
+                    .get::<BorderColor>(entity)
+                    .map(|color| color.0.alpha()),
+                text: world
+                    .get::<Text>(entity)
+                    .map(|text| {
+                        text.sections
+                            .iter()
+                            .map(|section| section.style.color.alpha())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            };
+            world.entity_mut(entity).insert(base);
+        }
+        let Some(base) = world.entity_mut(entity).take::<FadeBaseAlpha>() else {
+            continue;
+        };
+        set_alpha(world, entity, &base, opacity);
+        world.entity_mut(entity).insert(base);
+    }
+}
+
+fn set_alpha(world: &mut World, entity: Entity, base: &FadeBaseAlpha, opacity: f32) {
+    if let (Some(alpha), Some(mut color)) =
+        (base.background, world.get_mut::<BackgroundColor>(entity))
+    {
+        color.0.set_alpha(alpha * opacity);
+    }
+    if let (Some(alpha), Some(mut color)) = (base.border, world.get_mut::<BorderColor>(entity)) {
+        color.0.set_alpha(alpha * opacity);
+    }
+    if let Some(mut text) = world.get_mut::<Text>(entity) {
+        for (section, alpha) in text.sections.iter_mut().zip(&base.text) {
+            section.style.color.set_alpha(alpha * opacity);
+        }
+    }
+}
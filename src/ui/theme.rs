@@ -0,0 +1,114 @@
+//! Everything the widgets need to know to draw themselves, in one resource.
+//!
+//! Every widget constructor takes the [`Theme`] and uses its sizes in place of hardcoded ones, so changing it changes the look
+//! of all widgets spawned afterwards. The colors in the theme mirror the [`CurrentColors`] resource, so palette files and
+//! live theme switching keep working. Changing either one changes both, see [`Theme::colors`].
+
+use bevy::{app::Plugin, asset::Handle, ecs::system::Resource, text::Font};
+
+use super::colors::CurrentColors;
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<Theme>();
+    }
+}
+
+/// The look of the ui. Insert your own to restyle the widgets.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct Theme {
+    /// The palette the widgets are drawn with. It is kept in sync with the [`CurrentColors`] at the start of every update,
+    /// when both changed in the same update the [`CurrentColors`] win. So inserting a theme also replaces the current colors
+    /// with the ones in the theme, start from the current theme to keep them.
+    pub colors: CurrentColors,
+    pub fonts: ThemeFonts,
+    pub font_sizes: FontSizes,
+    pub spacing: Spacing,
+    pub border_widths: BorderWidths,
+    pub corner_radii: CornerRadii,
+}
+
+/// The fonts used for each kind of text. The default handles use bevys built in font.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ThemeFonts {
+    /// Regular text
+    pub body: Handle<Font>,
+    /// Text on buttons
+    pub button: Handle<Font>,
+    /// Titles and headings
+    pub heading: Handle<Font>,
+}
+
+/// The typographic scale, in logical pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontSizes {
+    /// Small print, hints, and labels
+    pub caption: f32,
+    pub body: f32,
+    pub button: f32,
+    pub heading: f32,
+    pub title: f32,
+}
+
+impl Default for FontSizes {
+    fn default() -> Self {
+        Self {
+            caption: 20.0,
+            body: 28.0,
+            button: 40.0,
+            heading: 48.0,
+            title: 64.0,
+        }
+    }
+}
+
+/// The spacing scale used for margins and padding, in logical pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spacing {
+    pub small: f32,
+    pub medium: f32,
+    pub large: f32,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            small: 5.0,
+            medium: 10.0,
+            large: 20.0,
+        }
+    }
+}
+
+/// Widths of borders and outlines, in logical pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BorderWidths {
+    /// Outlines of selected buttons
+    pub thin: f32,
+    /// Dividers between sections of a widget
+    pub medium: f32,
+    /// Borders around panels
+    pub thick: f32,
+}
+
+impl Default for BorderWidths {
+    fn default() -> Self {
+        Self {
+            thin: 3.0,
+            medium: 5.0,
+            thick: 10.0,
+        }
+    }
+}
+
+/// Corner radii, in logical pixels. They are all 0 by default, so the widgets have square corners unless a theme rounds them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CornerRadii {
+    /// Buttons and other small controls
+    pub small: f32,
+    /// Panels
+    pub medium: f32,
+    pub large: f32,
+}
@@ -2,24 +2,29 @@ use bevy::{
     app::{Plugin, Update},
     ecs::{
         bundle::Bundle,
+        change_detection::DetectChanges,
         component::Component,
         entity::Entity,
-        query::{Added, Changed, With, Without},
+        query::{Added, With},
         removal_detection::RemovedComponents,
         system::{Commands, Query, Res},
+        world::Ref,
     },
     hierarchy::{BuildChildren, Children},
-    prelude::default,
     text::{Text, TextStyle},
     ui::{
         node_bundles::{ButtonBundle, TextBundle},
         widget::Button,
-        AlignItems, BackgroundColor, Interaction, JustifyContent, Outline, Style, UiRect, Val,
+        AlignItems, BackgroundColor, BorderRadius, Interaction, JustifyContent, Outline, Style,
+        UiRect, Val,
     },
 };
 use bevy_mod_picking::focus::PickingInteraction;
 
-use crate::ui::colors::CurrentColors;
+use crate::ui::{
+    colors::{ColorVariant, CurrentColors, PaletteRole},
+    theme::Theme,
+};
 
 use super::{DisabledButton, SelectedButton};
 
@@ -37,56 +42,70 @@ impl Plugin for BasicButtonPlugin {
 #[derive(Component)]
 pub struct BasicButton;
 
+/// Derives the background of every basic button from [`PaletteRole::BackgroundDark`], lightened while hovered, darkened while
+/// pressed, and desaturated while disabled
+#[allow(clippy::type_complexity)]
 fn handle_basic_button_visuals(
     mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &Children),
         (
-            Changed<Interaction>,
-            With<Button>,
-            Without<DisabledButton>,
-            With<BasicButton>,
+            Entity,
+            Ref<Interaction>,
+            Option<Ref<DisabledButton>>,
+            &mut BackgroundColor,
+            &Children,
         ),
+        (With<Button>, With<BasicButton>),
     >,
+    mut removed_disabled_events: RemovedComponents<DisabledButton>,
     mut children_text_color: Query<&mut Text>,
     colors: Res<CurrentColors>,
 ) {
-    for (interaction, mut color, children) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                *color = BackgroundColor::from(colors.highlight());
-            }
-            Interaction::Hovered => {
-                *color = BackgroundColor::from(colors.interactive());
-                for &child in children.iter() {
-                    if let Ok(mut text) = children_text_color.get_mut(child) {
-                        text.sections[0].style.color = colors.light_text();
-                    }
-                }
-            }
-            Interaction::None => {
-                *color = BackgroundColor::from(colors.background_dark());
-                for &child in children.iter() {
-                    if let Ok(mut text) = children_text_color.get_mut(child) {
-                        text.sections[0].style.color = colors.light_text();
-                    }
-                }
+    let re_enabled: Vec<Entity> = removed_disabled_events.read().collect();
+    for (entity, interaction, disabled, mut color, children) in &mut interaction_query {
+        let disabled_changed = disabled
+            .as_ref()
+            .is_some_and(|disabled| disabled.is_added())
+            || re_enabled.contains(&entity);
+        // Restyle every button when the colors change, not just the ones being interacted with
+        if !interaction.is_changed() && !colors.is_changed() && !disabled_changed {
+            continue;
+        }
+        let (variant, text_color) = match (disabled.is_some(), *interaction) {
+            (true, _) => (ColorVariant::Disabled, colors.disabled()),
+            (false, Interaction::Pressed) => (ColorVariant::Pressed, colors.light_text()),
+            (false, Interaction::Hovered) => (ColorVariant::Hovered, colors.light_text()),
+            (false, Interaction::None) => (ColorVariant::Base, colors.light_text()),
+        };
+        *color = BackgroundColor::from(colors.color_variant(PaletteRole::BackgroundDark, variant));
+        for &child in children.iter() {
+            if let Ok(mut text) = children_text_color.get_mut(child) {
+                text.sections[0].style.color = text_color;
             }
         }
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn handle_selected_outlines(
     mut added_selection_query: Query<
         Entity,
         (Added<SelectedButton>, With<Button>, With<BasicButton>),
     >,
     mut removed_selected_events: RemovedComponents<SelectedButton>,
+    mut selected_outlines: Query<&mut Outline, (With<SelectedButton>, With<BasicButton>)>,
     colors: Res<CurrentColors>,
+    theme: Res<Theme>,
     mut commands: Commands,
 ) {
+    if colors.is_changed() {
+        for mut outline in selected_outlines.iter_mut() {
+            outline.color = colors.accent();
+        }
+    }
+
     for entity in &mut added_selection_query {
         commands.entity(entity).insert(Outline {
-            width: Val::Px(3.0),
+            width: Val::Px(theme.border_widths.thin),
             offset: Val::Px(0.0),
             color: colors.accent(),
         });
@@ -102,7 +121,8 @@ fn handle_selected_outlines(
 pub struct BasicButtonStyle<B: Bundle> {
     pub bundle: Option<B>,
     pub text: String,
-    pub font_size: f32,
+    /// The size of the text, None uses the [`Theme`]s button font size
+    pub font_size: Option<f32>,
 }
 
 impl<B> Default for BasicButtonStyle<B>
@@ -113,7 +133,7 @@ where
         Self {
             bundle: Default::default(),
             text: Default::default(),
-            font_size: 40.0,
+            font_size: None,
         }
     }
 }
@@ -122,23 +142,26 @@ pub fn basic_button<T>(
     button_marker: T,
     button_style: BasicButtonStyle<impl Bundle>,
     commands: &mut Commands,
-    colors: &CurrentColors,
+    theme: &Theme,
 ) -> Entity
 where
     T: Component,
 {
+    let colors = &theme.colors;
+    let font_size = button_style.font_size.unwrap_or(theme.font_sizes.button);
     let mut entity = commands.spawn((
         ButtonBundle {
             style: Style {
                 width: Val::Auto,
-                height: Val::Px(button_style.font_size + 10.0),
-                margin: UiRect::new(Val::Px(10.0), Val::Px(10.0), Val::Px(10.0), Val::Px(10.0)),
-                padding: UiRect::all(Val::Px(10.0)),
+                height: Val::Px(font_size + theme.spacing.medium),
+                margin: UiRect::all(Val::Px(theme.spacing.medium)),
+                padding: UiRect::all(Val::Px(theme.spacing.medium)),
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
                 ..Default::default()
             },
             background_color: BackgroundColor::from(colors.background()),
+            border_radius: BorderRadius::all(Val::Px(theme.corner_radii.small)),
             ..Default::default()
         },
         button_marker,
@@ -151,9 +174,9 @@ where
             TextBundle::from_section(
                 &button_style.text,
                 TextStyle {
-                    font_size: button_style.font_size,
+                    font: theme.fonts.button.clone(),
+                    font_size,
                     color: colors.light_text(),
-                    ..default()
                 },
             ),
             PickingInteraction::default(),
@@ -1,12 +1,15 @@
 use bevy::app::Plugin;
 
+use crate::ui::theme::Theme;
+
 use self::{
-    button::ButtonCorePlugin, modal::ModalPlugin, scroll::ScrollCorePlugin,
-    tabbed_content::TabbedContentPlugin,
+    button::ButtonCorePlugin, modal::ModalPlugin, safe_area::SafeAreaPlugin,
+    scroll::ScrollCorePlugin, tabbed_content::TabbedContentPlugin,
 };
 
 pub mod button;
 pub mod modal;
+pub mod safe_area;
 pub mod scroll;
 pub mod scroll_container;
 pub mod tabbed_content;
@@ -20,6 +23,9 @@ impl Plugin for WidgetsPlugin {
             ButtonCorePlugin,
             TabbedContentPlugin,
             ScrollCorePlugin,
+            SafeAreaPlugin,
         ));
+        // The button systems read the theme, so the widgets work without the `ThemePlugin`
+        app.init_resource::<Theme>();
     }
 }
@@ -8,12 +8,16 @@ use bevy::{
     }, hierarchy::{BuildChildren, DespawnRecursiveExt}, prelude::default, text::TextStyle, ui::{
         node_bundles::{ButtonBundle, NodeBundle, TextBundle},
         widget::Button,
-        AlignItems, BackgroundColor, FlexDirection, FocusPolicy, Interaction, JustifyContent,
-        PositionType, Style, UiRect, Val,
+        AlignItems, BackgroundColor, BorderRadius, FlexDirection, FocusPolicy, Interaction,
+        JustifyContent, PositionType, Style, UiRect, Val,
     }
 };
 
-use crate::ui::{colors::CurrentColors, UI_MODAL_LAYER};
+use crate::ui::{
+    colors::{PaletteRole, ThemedBackground, ThemedBorder},
+    layers::{UiLayer, UiLayers},
+    theme::Theme,
+};
 
 use super::button::BasicButton;
 
@@ -25,6 +29,7 @@ impl Plugin for ModalPlugin {
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn modal_button_interaction(
     mut interaction_query: Query<
         (&Interaction, &ModalCloseButtonMarker),
@@ -67,19 +72,20 @@ pub struct ModalStyle<B: Bundle> {
 pub fn modal_panel<T>(
     menu_type: T,
     modal_style: ModalStyle<impl Bundle>,
-    colors: &CurrentColors,
+    theme: &Theme,
     commands: &mut Commands,
 ) -> Entity
 where
     T: Component,
 {
+    let colors = &theme.colors;
     let modal_size = match modal_style.modal_size {
         None => (Val::Auto, Val::Auto),
         Some(size) => size,
     };
 
     let border = match modal_style.outline {
-        true => UiRect::all(Val::Px(10.0)),
+        true => UiRect::all(Val::Px(theme.border_widths.thick)),
         false => UiRect::all(Val::Px(0.0)),
     };
 
@@ -96,10 +102,11 @@ where
             },
             background_color: Color::BLACK.with_alpha(0.5).into(),
             focus_policy: FocusPolicy::Block,
-            z_index: bevy::ui::ZIndex::Global(UI_MODAL_LAYER),
+            // Start on the default modal layer so the modal is drawn over screens even without the `UiLayersPlugin`
+            z_index: UiLayers::default().z_index(UiLayer::Modal, 0),
             ..default()
         },))
-        .insert(menu_type)
+        .insert((menu_type, UiLayer::Modal))
         .id();
 
     if modal_style.can_close {
@@ -114,7 +121,7 @@ where
             style: Style {
                 width: modal_size.0,
                 height: modal_size.1,
-                padding: UiRect::all(Val::Px(5.0)),
+                padding: UiRect::all(Val::Px(theme.spacing.small)),
                 justify_content: JustifyContent::Start,
                 align_items: AlignItems::Center,
                 position_type: PositionType::Relative,
@@ -125,8 +132,13 @@ where
             focus_policy: FocusPolicy::Block,
             background_color: colors.background().into(),
             border_color: colors.background_dark().into(),
+            border_radius: BorderRadius::all(Val::Px(theme.corner_radii.medium)),
             ..default()
         })
+        .insert((
+            ThemedBackground(PaletteRole::Background),
+            ThemedBorder(PaletteRole::BackgroundDark),
+        ))
         .id();
 
     if modal_style.can_close {
@@ -151,19 +163,15 @@ where
                     .insert(ButtonBundle {
                         style: Style {
                             width: Val::Auto,
-                            height: Val::Px(50.0),
-                            margin: UiRect::new(
-                                Val::Px(20.0),
-                                Val::Px(20.0),
-                                Val::Px(20.0),
-                                Val::Px(20.0),
-                            ),
-                            padding: UiRect::all(Val::Px(10.0)),
+                            height: Val::Px(theme.font_sizes.button + theme.spacing.medium),
+                            margin: UiRect::all(Val::Px(theme.spacing.large)),
+                            padding: UiRect::all(Val::Px(theme.spacing.medium)),
                             justify_content: JustifyContent::Center,
                             align_items: AlignItems::Center,
                             ..Default::default()
                         },
                         background_color: BackgroundColor::from(colors.background_dark()),
+                        border_radius: BorderRadius::all(Val::Px(theme.corner_radii.small)),
                         ..Default::default()
                     })
                     .insert(ModalCloseButtonMarker(root))
@@ -172,9 +180,9 @@ where
                         parent.spawn(TextBundle::from_section(
                             "X",
                             TextStyle {
-                                font_size: 40.0,
+                                font: theme.fonts.button.clone(),
+                                font_size: theme.font_sizes.button,
                                 color: colors.light_text(),
-                                ..default()
                             },
                         ));
                     });
@@ -193,7 +201,7 @@ where
             style: Style {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
-                margin: UiRect::all(Val::Px(10.0)),
+                margin: UiRect::all(Val::Px(theme.spacing.medium)),
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
                 position_type: PositionType::Relative,
@@ -0,0 +1,156 @@
+use bevy::{
+    app::{Plugin, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::{Changed, With, Without},
+        system::{Commands, Query},
+    },
+    hierarchy::{BuildChildren, Children},
+    prelude::default,
+    ui::{
+        node_bundles::NodeBundle, AlignItems, JustifyContent, Node, PositionType, Style, UiRect,
+        Val,
+    },
+};
+
+pub struct SafeAreaPlugin;
+
+impl Plugin for SafeAreaPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Update, (update_safe_area_margins, letterbox_safe_areas));
+    }
+}
+
+/// Settings for a safe area, also the component on the safe area root. Change it at runtime to update the safe area.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct SafeArea {
+    /// The width / height ratio the content is kept at, letterboxed in the middle of the screen. None fills the whole area
+    pub aspect_ratio: Option<f32>,
+    /// Space kept clear around the edges of the screen, for things like notches, rounded corners, and tv overscan
+    pub margins: UiRect,
+}
+
+impl Default for SafeArea {
+    fn default() -> Self {
+        Self {
+            aspect_ratio: Some(16.0 / 9.0),
+            margins: UiRect::all(Val::Px(0.0)),
+        }
+    }
+}
+
+/// A marker marking the node inside a safe area that all user content should be parented to
+#[derive(Component)]
+pub struct SafeAreaContent;
+
+/// Construct and spawn a new safe area spanning the whole screen. Returns the [`SafeAreaContent`] entity that the scenes ui
+/// should be parented to.
+pub fn safe_area<T>(menu_type: T, settings: SafeArea, commands: &mut Commands) -> Entity
+where
+    T: Component,
+{
+    // Root level node, spanning the whole screen and keeping the margins clear
+    let root = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    padding: settings.margins,
+                    ..default()
+                },
+                ..default()
+            },
+            settings,
+            menu_type,
+        ))
+        .id();
+
+    // Fills the space inside the margins so that its size can be measured to letterbox the content
+    let bounds = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+
+    let content = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                ..default()
+            },
+            SafeAreaContent,
+        ))
+        .id();
+
+    commands.entity(bounds).push_children(&[content]);
+    commands.entity(root).push_children(&[bounds]);
+
+    content
+}
+
+fn update_safe_area_margins(mut safe_areas: Query<(&SafeArea, &mut Style), Changed<SafeArea>>) {
+    for (safe_area, mut style) in safe_areas.iter_mut() {
+        style.padding = safe_area.margins;
+    }
+}
+
+/// Sizes the content of every safe area to the largest rect of its aspect ratio that fits inside the bounds
+fn letterbox_safe_areas(
+    safe_areas: Query<(&SafeArea, &Children)>,
+    bounds: Query<(&Node, &Children)>,
+    mut contents: Query<&mut Style, (With<SafeAreaContent>, Without<SafeArea>)>,
+) {
+    for (safe_area, children) in safe_areas.iter() {
+        let Some((bounds_node, bounds_children)) = children
+            .first()
+            .and_then(|bounds_entity| bounds.get(*bounds_entity).ok())
+        else {
+            continue;
+        };
+        let Some(mut style) = bounds_children
+            .first()
+            .and_then(|content| contents.get_mut(*content).ok())
+        else {
+            continue;
+        };
+
+        let size = bounds_node.size();
+        let (width, height) = match safe_area.aspect_ratio {
+            Some(aspect_ratio) if size.x > 0.0 && size.y > 0.0 => {
+                let bounds_ratio = size.x / size.y;
+                match bounds_ratio > aspect_ratio {
+                    // Wider than the content, so the bars go on the left and right
+                    true => (
+                        Val::Percent(100.0 * aspect_ratio / bounds_ratio),
+                        Val::Percent(100.0),
+                    ),
+                    false => (
+                        Val::Percent(100.0),
+                        Val::Percent(100.0 * bounds_ratio / aspect_ratio),
+                    ),
+                }
+            }
+            _ => (Val::Percent(100.0), Val::Percent(100.0)),
+        };
+
+        // Only write when something changed so the layout isn't recomputed every frame
+        if style.width != width || style.height != height {
+            style.width = width;
+            style.height = height;
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::app::{Plugin, Update};
 use bevy::ecs::component::Component;
 use bevy::ecs::event::{EventReader, EventWriter};
-use bevy::ecs::query::Added;
+use bevy::ecs::query::{Added, Without};
 use bevy::ecs::system::{Commands, Query};
 use bevy::ecs::{entity::Entity, event::Event};
 use bevy::hierarchy::Parent;
@@ -35,6 +35,18 @@ pub struct Scrollable {
     position: f32,
 }
 
+impl Scrollable {
+    /// Creates a new scrollable that starts scrolled to the given position
+    pub fn new(position: f32) -> Self {
+        Self { position }
+    }
+
+    /// How far the content is currently scrolled
+    pub fn position(&self) -> f32 {
+        self.position
+    }
+}
+
 pub fn scroll(
     mut event: ListenerMut<ScrollEvent>,
     mut query_list: Query<(&mut Scrollable, &mut Style, &Parent, &Node)>,
@@ -84,7 +96,10 @@ fn send_scroll_events(
     }
 }
 
-fn setup_scrollables(query: Query<Entity, Added<On<ScrollEvent>>>, mut commands: Commands) {
+fn setup_scrollables(
+    query: Query<Entity, (Added<On<ScrollEvent>>, Without<Scrollable>)>,
+    mut commands: Commands,
+) {
     for entity in query.iter() {
         commands.entity(entity).insert(Scrollable::default());
     }
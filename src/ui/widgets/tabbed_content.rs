@@ -6,7 +6,7 @@ use bevy::{
         entity::Entity,
         query::{With, Without},
         system::{Commands, Query},
-        world::Ref,
+        world::{Ref, World},
     },
     hierarchy::{BuildChildren, Children, Parent},
     prelude::default,
@@ -16,7 +16,10 @@ use bevy::{
     },
 };
 
-use crate::ui::colors::CurrentColors;
+use crate::ui::{
+    colors::{PaletteRole, ThemedBackground, ThemedBorder},
+    theme::Theme,
+};
 
 use super::button::{basic_button, BasicButton, BasicButtonStyle, DisabledButton, SelectedButton};
 
@@ -28,6 +31,7 @@ impl Plugin for TabbedContentPlugin {
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn tab_button_interaction(
     tab_buttons: Query<
         (
@@ -75,6 +79,46 @@ pub fn tab_button_interaction(
     }
 }
 
+/// The root of a tabbed content container. Holds the button for each tab in order
+#[derive(Component)]
+pub struct TabbedContent {
+    buttons: Vec<Entity>,
+}
+
+impl TabbedContent {
+    /// The button for each tab, in the order the tabs were given in
+    pub fn buttons(&self) -> &[Entity] {
+        &self.buttons
+    }
+}
+
+/// Opens the tab at the given index of the tabbed content container with the given root, closing all the others
+pub fn select_tab(world: &mut World, root: Entity, index: usize) {
+    let Some(tabbed_content) = world.get::<TabbedContent>(root) else {
+        return;
+    };
+    let buttons = tabbed_content.buttons.clone();
+
+    for (i, button) in buttons.into_iter().enumerate() {
+        let Some(content) = world
+            .get::<TabContentEntity>(button)
+            .map(|content| content.0)
+        else {
+            continue;
+        };
+        let display = if i == index {
+            world.entity_mut(button).insert(SelectedButton);
+            Display::DEFAULT
+        } else {
+            world.entity_mut(button).remove::<SelectedButton>();
+            Display::None
+        };
+        if let Some(mut style) = world.get_mut::<Style>(content) {
+            style.display = display;
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct TabContentEntity(Entity);
 
@@ -95,13 +139,15 @@ pub struct TabbedContentSettings {
 pub fn tabbed_content<T>(
     menu_type: T,
     tab_settings: TabbedContentSettings,
-    colors: &CurrentColors,
+    theme: &Theme,
     commands: &mut Commands,
 ) -> (Entity, Vec<(Entity, String)>)
 where
     T: Component,
 {
+    let colors = &theme.colors;
     let mut tab_entities = Vec::with_capacity(tab_settings.tabs.len());
+    let mut buttons = Vec::with_capacity(tab_settings.tabs.len());
 
     let root = commands
         .spawn((NodeBundle {
@@ -112,14 +158,14 @@ where
                 align_items: AlignItems::Center,
                 position_type: PositionType::Relative,
                 flex_direction: FlexDirection::Column,
-                border: UiRect::all(Val::Px(10.0)),
+                border: UiRect::all(Val::Px(theme.border_widths.thick)),
                 ..default()
             },
             background_color: colors.background().into(),
             focus_policy: FocusPolicy::Block,
             ..default()
         },))
-        .insert(menu_type)
+        .insert((menu_type, ThemedBackground(PaletteRole::Background)))
         .id();
 
     let tab_buttons = commands
@@ -131,12 +177,13 @@ where
                 align_items: AlignItems::Center,
                 position_type: PositionType::Relative,
                 flex_direction: FlexDirection::Row,
-                border: UiRect::bottom(Val::Px(5.0)),
+                border: UiRect::bottom(Val::Px(theme.border_widths.medium)),
                 ..default()
             },
             border_color: colors.background_light().into(),
             ..default()
         },))
+        .insert(ThemedBorder(PaletteRole::BackgroundLight))
         .id();
 
     let tab_buttons_inside = commands
@@ -201,18 +248,20 @@ where
         let button_style = BasicButtonStyle {
             bundle: Some(TabContentEntity(content)),
             text: tab.clone(),
-            font_size: 40.0,
+            font_size: None,
         };
-        let button = basic_button(TabContentButton, button_style, commands, colors);
+        let button = basic_button(TabContentButton, button_style, commands, theme);
         if i == tab_settings.open_tab {
             commands.entity(button).insert(SelectedButton);
         }
+        buttons.push(button);
         commands.entity(tab_buttons_inside).push_children(&[button]);
         commands.entity(tab_contents).push_children(&[content]);
     }
 
     commands
         .entity(root)
+        .insert(TabbedContent { buttons })
         .push_children(&[tab_buttons, tab_contents]);
 
     (root, tab_entities)
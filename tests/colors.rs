@@ -0,0 +1,161 @@
+use bevy::{asset::LoadState, prelude::*};
+use ns_core::ui::colors::*;
+
+fn failures(palette: GameColorPalette, level: ContrastLevel) -> Vec<String> {
+    palette
+        .check_contrast(level)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn contrast_ratio_matches_wcag() {
+    assert!((contrast_ratio(Color::BLACK, Color::WHITE) - 21.0).abs() < 0.01);
+    assert!((contrast_ratio(Color::WHITE, Color::BLACK) - 21.0).abs() < 0.01);
+    assert_eq!(contrast_ratio(Color::WHITE, Color::WHITE), 1.0);
+}
+
+#[test]
+fn light_palette_passes_aa() {
+    assert_eq!(
+        failures(GameColorPalette::light(), ContrastLevel::Aa),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn high_contrast_palette_passes_aaa() {
+    assert_eq!(
+        failures(GameColorPalette::high_contrast(), ContrastLevel::Aaa),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn failing_pairs_are_reported() {
+    let palette = GameColorPalette {
+        light_text: Color::srgb(0.5, 0.5, 0.5),
+        ..GameColorPalette::high_contrast()
+    };
+
+    let failures = palette.check_contrast(ContrastLevel::Aaa);
+
+    assert!(!failures.is_empty());
+    assert!(failures
+        .iter()
+        .all(|failure| failure.text == PaletteRole::LightText
+            && failure.ratio < failure.required
+            && failure.required == ContrastLevel::Aaa.min_ratio()));
+}
+
+fn definition_without_optional_colors(palette: &GameColorPalette) -> PaletteDefinition {
+    PaletteDefinition {
+        success: None,
+        warning: None,
+        danger: None,
+        info: None,
+        disabled: None,
+        ..PaletteDefinition::from(palette)
+    }
+}
+
+#[test]
+fn left_out_colors_are_worked_out_from_the_palette() {
+    for palette in [GameColorPalette::dark(), GameColorPalette::light()] {
+        let loaded: GameColorPalette = definition_without_optional_colors(&palette)
+            .try_into()
+            .unwrap();
+        // Status colors are used for text and icons, so they need to be readable on the background
+        for status in [loaded.success, loaded.warning, loaded.danger, loaded.info] {
+            assert!(
+                contrast_ratio(status, loaded.background) >= ContrastLevel::AaLarge.min_ratio()
+            );
+        }
+        // Disabled text stands out less than regular text, but still stands out
+        let disabled = contrast_ratio(loaded.disabled, loaded.background);
+        let text = contrast_ratio(loaded.light_text, loaded.background)
+            .max(contrast_ratio(loaded.dark_text, loaded.background));
+        assert!(
+            disabled > 1.5 && disabled < text,
+            "{disabled} against {text}"
+        );
+    }
+}
+
+/// Makes the palette at the given path in `tests/assets` active and waits for it to be loaded
+fn activate_palette(app: &mut App, path: &'static str) {
+    let handle = app
+        .world()
+        .resource::<AssetServer>()
+        .load::<GameColorPalette>(path);
+    app.insert_resource(ActivePalette(handle.clone()));
+    for _ in 0..500 {
+        app.update();
+        let load_state = app.world().resource::<AssetServer>().load_state(&handle);
+        if let LoadState::Loaded | LoadState::Failed(_) = load_state {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(2));
+    }
+    // The palette is copied into the current colors in the update after it finished loading
+    app.update();
+}
+
+#[test]
+fn palette_files_become_the_current_colors() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: "tests/assets".into(),
+            ..default()
+        },
+        GameColorsPlugin {
+            custom_colors: None,
+        },
+    ));
+
+    activate_palette(&mut app, "dusk.palette.ron");
+    let colors = *app.world().resource::<CurrentColors>();
+    assert_eq!(colors.background(), Srgba::hex("112233").unwrap().into());
+    assert_eq!(colors.danger(), Srgba::hex("C2564A").unwrap().into());
+
+    activate_palette(&mut app, "dawn.palette.json");
+    assert_eq!(
+        app.world().resource::<CurrentColors>().background(),
+        Srgba::hex("ABCDEF").unwrap().into()
+    );
+}
+
+#[test]
+fn modified_palettes_update_the_current_colors() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: "tests/assets".into(),
+            ..default()
+        },
+        GameColorsPlugin {
+            custom_colors: None,
+        },
+    ));
+    activate_palette(&mut app, "dusk.palette.ron");
+
+    // Changing the asset sends the same `AssetEvent::Modified` that a hot reload of the file ends in
+    let handle = app.world().resource::<ActivePalette>().0.clone();
+    app.world_mut()
+        .resource_mut::<Assets<GameColorPalette>>()
+        .get_mut(&handle)
+        .unwrap()
+        .background = Color::WHITE;
+    // The event is sent at the end of this update and read at the start of the next
+    app.update();
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<CurrentColors>().background(),
+        Color::WHITE
+    );
+}
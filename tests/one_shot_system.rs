@@ -0,0 +1,380 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use ns_core::one_shot_system::*;
+
+#[derive(Component, TypePath)]
+struct Tooltip;
+
+#[derive(Resource, Default)]
+struct Ticks(u32);
+
+#[derive(Resource, Default)]
+struct Runs {
+    added: u32,
+    removed: u32,
+}
+
+fn errors(app: &App) -> Vec<OneShotSystemError> {
+    let events = app.world().resource::<Events<OneShotSystemError>>();
+    events.get_reader().read(events).copied().collect()
+}
+
+fn count_added(In(_): In<Entity>, mut runs: ResMut<Runs>) {
+    runs.added += 1;
+}
+
+fn count_removed(In(_): In<Entity>, mut runs: ResMut<Runs>) {
+    runs.removed += 1;
+}
+
+#[test]
+fn systems_with_input_and_output_return_their_output() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.register_one_shot_system_with_io(marker_component::<Tooltip>(), |In(width): In<u32>| {
+        width * 2
+    });
+
+    assert_eq!(
+        app.world_mut()
+            .run_marker_system_with::<Tooltip, u32, u32>(21),
+        Ok(42)
+    );
+    let mismatched = app
+        .world_mut()
+        .run_marker_system_with::<Tooltip, u8, u32>(21);
+    assert!(matches!(
+        mismatched,
+        Err(OneShotSystemError::MismatchedTypes { .. })
+    ));
+}
+
+#[test]
+fn markers_can_have_a_system_for_every_label() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.register_labeled_one_shot_system(marker_component::<Tooltip>(), "open", || "opened");
+    app.register_labeled_one_shot_system(marker_component::<Tooltip>(), "close", || "closed");
+
+    assert_eq!(
+        app.world_mut()
+            .run_labeled_marker_system_with::<Tooltip, (), &str>("open", ()),
+        Ok("opened")
+    );
+    assert_eq!(
+        app.world_mut()
+            .run_labeled_marker_system_with::<Tooltip, (), &str>("close", ()),
+        Ok("closed")
+    );
+    let mut labels: Vec<_> = app
+        .world()
+        .resource::<OneShotSystemIds>()
+        .labels(Tooltip::type_path())
+        .collect();
+    labels.sort();
+    assert_eq!(labels, vec!["close", "open"]);
+
+    // Registering a label twice replaces the first system and is reported
+    app.register_labeled_one_shot_system(marker_component::<Tooltip>(), "open", || "reopened");
+    assert_eq!(
+        app.world_mut()
+            .run_labeled_marker_system_with::<Tooltip, (), &str>("open", ()),
+        Ok("reopened")
+    );
+    assert_eq!(
+        errors(&app),
+        vec![OneShotSystemError::DuplicateRegistration {
+            marker: Tooltip::type_path(),
+            label: "open",
+        }]
+    );
+}
+
+#[test]
+fn marker_systems_can_be_run_from_commands() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.init_resource::<Ticks>();
+    app.register_one_shot_system(marker_component::<Tooltip>(), |mut ticks: ResMut<Ticks>| {
+        ticks.0 += 1
+    });
+    app.register_labeled_one_shot_system(
+        marker_component::<Tooltip>(),
+        "reset",
+        |mut ticks: ResMut<Ticks>| ticks.0 = 0,
+    );
+
+    let mut commands = app.world_mut().commands();
+    commands.run_marker_system::<Tooltip>();
+    commands.run_marker_system::<Tooltip>();
+    app.world_mut().flush();
+    assert_eq!(app.world().resource::<Ticks>().0, 2);
+
+    app.world_mut()
+        .commands()
+        .run_labeled_marker_system::<Tooltip>("reset");
+    app.world_mut().flush();
+    assert_eq!(app.world().resource::<Ticks>().0, 0);
+
+    // Nothing is registered under this label, so it is reported instead of panicking
+    app.world_mut()
+        .commands()
+        .run_labeled_marker_system::<Tooltip>("missing");
+    app.world_mut().flush();
+    assert_eq!(
+        errors(&app),
+        vec![OneShotSystemError::NotRegistered {
+            marker: Tooltip::type_path(),
+            label: "missing",
+        }]
+    );
+}
+
+#[test]
+fn triggered_systems_run_when_the_marker_is_added_or_removed() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.init_resource::<Runs>();
+    app.register_triggered_one_shot_system(
+        marker_component::<Tooltip>(),
+        MarkerTrigger::Added,
+        count_added,
+    );
+    app.register_triggered_one_shot_system(
+        marker_component::<Tooltip>(),
+        MarkerTrigger::Removed,
+        count_removed,
+    );
+
+    let entity = app.world_mut().spawn(Tooltip).id();
+    app.world_mut().flush();
+    app.world_mut().entity_mut(entity).remove::<Tooltip>();
+    app.world_mut().flush();
+
+    let runs = app.world().resource::<Runs>();
+    assert_eq!((runs.added, runs.removed), (1, 1));
+}
+
+#[test]
+fn registering_a_triggered_system_twice_replaces_it_without_observing_twice() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.init_resource::<Runs>();
+    app.register_triggered_one_shot_system(
+        marker_component::<Tooltip>(),
+        MarkerTrigger::Added,
+        |In(_): In<Entity>, mut runs: ResMut<Runs>| runs.added += 100,
+    );
+    app.register_triggered_one_shot_system(
+        marker_component::<Tooltip>(),
+        MarkerTrigger::Added,
+        count_added,
+    );
+
+    app.world_mut().spawn(Tooltip);
+    app.world_mut().flush();
+
+    assert_eq!(app.world().resource::<Runs>().added, 1);
+}
+
+#[test]
+fn unregistered_triggered_systems_stop_running_and_can_be_registered_again() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.init_resource::<Runs>();
+    app.register_triggered_one_shot_system(
+        marker_component::<Tooltip>(),
+        MarkerTrigger::Added,
+        count_added,
+    );
+    assert_eq!(
+        app.world_mut()
+            .unregister_marker_system::<Tooltip>(ON_ADD_LABEL),
+        Ok(())
+    );
+
+    // Without the system its observer is gone too, so nothing runs and nothing is reported missing
+    app.world_mut().spawn(Tooltip);
+    app.world_mut().flush();
+    assert_eq!(app.world().resource::<Runs>().added, 0);
+    assert_eq!(errors(&app), vec![]);
+
+    app.register_triggered_one_shot_system(
+        marker_component::<Tooltip>(),
+        MarkerTrigger::Added,
+        count_added,
+    );
+    app.world_mut().spawn(Tooltip);
+    app.world_mut().flush();
+    assert_eq!(app.world().resource::<Runs>().added, 1);
+    assert_eq!(errors(&app), vec![]);
+}
+
+#[test]
+fn unregistering_removes_the_system_from_the_world() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.register_labeled_one_shot_system(marker_component::<Tooltip>(), "open", || {});
+    let system = app
+        .world()
+        .resource::<OneShotSystemIds>()
+        .get(Tooltip::type_path(), "open")
+        .unwrap();
+
+    assert_eq!(
+        app.world_mut().unregister_marker_system::<Tooltip>("open"),
+        Ok(())
+    );
+    assert!(app.world().get_entity(system.entity()).is_none());
+
+    let missing = OneShotSystemError::NotRegistered {
+        marker: Tooltip::type_path(),
+        label: "open",
+    };
+    assert_eq!(
+        app.world_mut().unregister_marker_system::<Tooltip>("open"),
+        Err(missing)
+    );
+    assert_eq!(
+        app.world_mut().run_labeled_marker_system::<Tooltip>("open"),
+        Err(missing)
+    );
+    assert_eq!(errors(&app), vec![missing, missing]);
+}
+
+#[test]
+fn replacing_a_system_is_not_reported_as_a_duplicate() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.register_one_shot_system_with_io(marker_component::<Tooltip>(), || 1u8);
+    let old = app
+        .world()
+        .resource::<OneShotSystemIds>()
+        .get_typed::<(), u8>(Tooltip::type_path(), DEFAULT_LABEL)
+        .unwrap();
+
+    app.world_mut()
+        .replace_marker_system::<Tooltip, (), u8, _>(DEFAULT_LABEL, || 2u8);
+
+    assert_eq!(
+        app.world_mut()
+            .run_marker_system_with::<Tooltip, (), u8>(()),
+        Ok(2)
+    );
+    assert!(app.world().get_entity(old.entity()).is_none());
+    assert_eq!(errors(&app), vec![]);
+}
+
+#[test]
+fn registry_errors_are_returned_and_sent_as_events() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.register_labeled_one_shot_system(marker_component::<Tooltip>(), "close", || 1u8);
+    app.register_labeled_one_shot_system(marker_component::<Tooltip>(), "close", || 2u8);
+    // Running itself fails, since the system is taken out of the world while it runs
+    app.register_one_shot_system(marker_component::<Tooltip>(), |world: &mut World| {
+        let _ = world.run_marker_system::<Tooltip>();
+    });
+
+    assert_eq!(
+        app.world_mut()
+            .run_labeled_marker_system_with::<Tooltip, (), u8>("close", ()),
+        Ok(2)
+    );
+    let mismatched = app
+        .world_mut()
+        .run_labeled_marker_system_with::<Tooltip, u32, u8>("close", 1);
+    assert!(matches!(
+        mismatched,
+        Err(OneShotSystemError::MismatchedTypes { .. })
+    ));
+    assert_eq!(app.world_mut().run_marker_system::<Tooltip>(), Ok(()));
+
+    assert_eq!(
+        errors(&app),
+        vec![
+            OneShotSystemError::DuplicateRegistration {
+                marker: Tooltip::type_path(),
+                label: "close",
+            },
+            mismatched.unwrap_err(),
+            OneShotSystemError::RunFailed {
+                marker: Tooltip::type_path(),
+                label: DEFAULT_LABEL,
+            },
+        ]
+    );
+}
+
+fn scheduling_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, OneShotSystemPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+    app.init_resource::<Ticks>();
+    app.register_one_shot_system(marker_component::<Tooltip>(), |mut ticks: ResMut<Ticks>| {
+        ticks.0 += 1
+    });
+    app
+}
+
+#[test]
+fn scheduled_systems_run_once_when_due() {
+    let mut app = scheduling_app();
+    let handle = app
+        .world_mut()
+        .schedule_labeled_marker_system::<Tooltip>(DEFAULT_LABEL, OneShotSchedule::AfterFrames(2));
+
+    app.update();
+    assert_eq!(app.world().resource::<Ticks>().0, 0);
+    app.update();
+    assert_eq!(app.world().resource::<Ticks>().0, 1);
+    assert!(!handle.is_scheduled(app.world()));
+
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<Ticks>().0, 1);
+}
+
+#[test]
+fn cancelled_repeating_systems_stop_running() {
+    let mut app = scheduling_app();
+    let handle = app.world_mut().schedule_labeled_marker_system::<Tooltip>(
+        DEFAULT_LABEL,
+        OneShotSchedule::Every(Duration::from_millis(250)),
+    );
+    for _ in 0..12 {
+        app.update();
+    }
+    let ticks = app.world().resource::<Ticks>().0;
+    assert!((3..=5).contains(&ticks), "ran {ticks} times");
+    assert!(handle.is_scheduled(app.world()));
+
+    app.world_mut().cancel_scheduled_system(handle);
+    // Cancelling twice does nothing
+    app.world_mut().cancel_scheduled_system(handle);
+    for _ in 0..12 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<Ticks>().0, ticks);
+}
+
+#[test]
+fn scheduled_systems_that_are_not_registered_are_reported() {
+    let mut app = scheduling_app();
+    app.world_mut()
+        .schedule_labeled_marker_system::<Tooltip>("missing", OneShotSchedule::AfterFrames(0));
+
+    app.update();
+
+    assert_eq!(
+        errors(&app),
+        vec![OneShotSystemError::NotRegistered {
+            marker: Tooltip::type_path(),
+            label: "missing",
+        }]
+    );
+}
@@ -0,0 +1,159 @@
+use bevy::{
+    ecs::system::RunSystemOnce,
+    input::InputPlugin,
+    prelude::*,
+    render::camera::CameraPlugin,
+    text::TextPlugin,
+    window::{PrimaryWindow, WindowResized, WindowResolution},
+};
+use ns_core::ui::widgets::safe_area::*;
+
+#[derive(Component)]
+struct Hud;
+
+fn app(width: f32, height: f32) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        WindowPlugin {
+            primary_window: Some(Window {
+                resolution: WindowResolution::new(width, height),
+                ..default()
+            }),
+            ..default()
+        },
+        AssetPlugin::default(),
+        ImagePlugin::default(),
+        TransformPlugin,
+        HierarchyPlugin,
+        CameraPlugin,
+        TextPlugin,
+        InputPlugin,
+    ));
+    // The ui plugin loads its shaders even without a renderer
+    app.init_asset::<Shader>()
+        .init_asset::<TextureAtlasLayout>();
+    app.add_plugins((bevy::ui::UiPlugin, SafeAreaPlugin));
+    app.world_mut().spawn(Camera2dBundle::default());
+    app
+}
+
+fn spawn_safe_area(app: &mut App, settings: SafeArea) -> Entity {
+    let content = app
+        .world_mut()
+        .run_system_once(move |mut commands: Commands| safe_area(Hud, settings, &mut commands));
+    settle(app);
+    content
+}
+
+/// The content is sized from the previous frame's layout, so it takes two updates to catch up
+fn settle(app: &mut App) {
+    app.update();
+    app.update();
+}
+
+/// Resizes the primary window the way winit would, including the event the camera listens to
+fn resize(app: &mut App, width: f32, height: f32) {
+    let world = app.world_mut();
+    let (window_entity, mut window) = world
+        .query_filtered::<(Entity, &mut Window), With<PrimaryWindow>>()
+        .single_mut(world);
+    window.resolution.set(width, height);
+    world.send_event(WindowResized {
+        window: window_entity,
+        width,
+        height,
+    });
+    settle(app);
+}
+
+fn content_size(app: &App, content: Entity) -> Vec2 {
+    app.world().get::<Node>(content).unwrap().size()
+}
+
+/// Layout rounds node sizes to whole pixels
+fn assert_size(actual: Vec2, expected: Vec2) {
+    assert!(
+        actual.abs_diff_eq(expected, 1.0),
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn wide_windows_are_pillarboxed() {
+    let mut app = app(1920.0, 1080.0);
+    let content = spawn_safe_area(
+        &mut app,
+        SafeArea {
+            aspect_ratio: Some(1.0),
+            ..default()
+        },
+    );
+
+    let style = app.world().get::<Style>(content).unwrap();
+    assert_eq!(style.width, Val::Percent(56.25));
+    assert_eq!(style.height, Val::Percent(100.0));
+    assert_size(content_size(&app, content), Vec2::new(1080.0, 1080.0));
+}
+
+#[test]
+fn resizing_the_window_moves_the_bars() {
+    let mut app = app(1920.0, 1080.0);
+    let content = spawn_safe_area(
+        &mut app,
+        SafeArea {
+            aspect_ratio: Some(1.0),
+            ..default()
+        },
+    );
+
+    resize(&mut app, 1000.0, 2000.0);
+
+    let style = app.world().get::<Style>(content).unwrap();
+    assert_eq!(style.width, Val::Percent(100.0));
+    assert_eq!(style.height, Val::Percent(50.0));
+    assert_size(content_size(&app, content), Vec2::new(1000.0, 1000.0));
+}
+
+#[test]
+fn margins_are_kept_clear_before_letterboxing() {
+    let mut app = app(1920.0, 1080.0);
+    let content = spawn_safe_area(
+        &mut app,
+        SafeArea {
+            aspect_ratio: Some(16.0 / 9.0),
+            margins: UiRect::all(Val::Px(60.0)),
+        },
+    );
+
+    // The 1800x960 space inside the margins is wider than 16:9
+    assert_size(
+        content_size(&app, content),
+        Vec2::new(960.0 * 16.0 / 9.0, 960.0),
+    );
+
+    let world = app.world_mut();
+    let (mut safe_area, style) = world.query::<(&mut SafeArea, &Style)>().single_mut(world);
+    assert_eq!(style.padding, UiRect::all(Val::Px(60.0)));
+    safe_area.margins = UiRect::all(Val::Px(0.0));
+    settle(&mut app);
+
+    assert_size(content_size(&app, content), Vec2::new(1920.0, 1080.0));
+}
+
+#[test]
+fn no_aspect_ratio_fills_the_space_inside_the_margins() {
+    let mut app = app(1920.0, 1080.0);
+    let content = spawn_safe_area(
+        &mut app,
+        SafeArea {
+            aspect_ratio: None,
+            margins: UiRect::horizontal(Val::Px(100.0)),
+        },
+    );
+
+    assert_size(content_size(&app, content), Vec2::new(1720.0, 1080.0));
+
+    resize(&mut app, 1000.0, 2000.0);
+    assert_size(content_size(&app, content), Vec2::new(800.0, 2000.0));
+}
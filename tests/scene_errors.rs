@@ -0,0 +1,52 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use ns_core::{
+    one_shot_system::{marker_component, OneShotSystemError},
+    ui::scenes::*,
+};
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum Game {
+    #[default]
+    Menu,
+    Playing,
+    Paused,
+}
+
+#[derive(Component, TypePath)]
+struct Hud;
+
+#[derive(Component, TypePath)]
+struct Unregistered;
+
+#[test]
+fn scene_registration_and_lookup_errors_are_sent_as_events() {
+    // Without the one shot system plugin, so the scenes have to set up the registry and events themselves
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin));
+    app.init_state::<Game>();
+    app.add_scene(marker_component::<Hud>(), || {}, Game::Playing);
+    app.add_scene(marker_component::<Hud>(), || {}, Game::Paused);
+    app.world_mut()
+        .commands()
+        .push_scene::<Unregistered>(LowerSceneBehavior::Keep);
+    app.update();
+
+    let events = app.world().resource::<Events<OneShotSystemError>>();
+    assert_eq!(
+        events
+            .get_reader()
+            .read(events)
+            .copied()
+            .collect::<Vec<_>>(),
+        vec![
+            OneShotSystemError::DuplicateRegistration {
+                marker: Hud::type_path(),
+                label: SCENE_SETUP_LABEL,
+            },
+            OneShotSystemError::NotRegistered {
+                marker: Unregistered::type_path(),
+                label: SCENE_SETUP_LABEL,
+            },
+        ]
+    );
+}
@@ -0,0 +1,166 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use ns_core::{
+    one_shot_system::{
+        marker_component, OneShotSystemError, OneShotSystemPlugin, OneShotSystemWorldExt,
+    },
+    ui::scenes::*,
+};
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum Screen {
+    #[default]
+    Menu,
+    Shop,
+}
+
+#[derive(Component, TypePath)]
+struct Hud;
+
+#[derive(Resource, Default)]
+struct Teardowns(u32);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.init_state::<Screen>();
+    app
+}
+
+fn hud_names(app: &mut App) -> Vec<String> {
+    app.world_mut()
+        .query_filtered::<&Name, With<Hud>>()
+        .iter(app.world())
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn errors(app: &App) -> Vec<OneShotSystemError> {
+    let events = app.world().resource::<Events<OneShotSystemError>>();
+    events.get_reader().read(events).copied().collect()
+}
+
+fn enter_shop(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<NextState<Screen>>()
+        .set(Screen::Shop);
+    app.update();
+}
+
+#[test]
+fn replaced_setup_rebuilds_the_spawned_scene() {
+    let mut app = app();
+    app.init_resource::<Teardowns>();
+    app.add_scene(
+        marker_component::<Hud>(),
+        |mut commands: Commands| {
+            commands.spawn((Hud, Name::new("old")));
+        },
+        Screen::Menu,
+    );
+    app.add_scene_teardown(
+        marker_component::<Hud>(),
+        |mut teardowns: ResMut<Teardowns>| teardowns.0 += 1,
+    );
+    app.update();
+    assert_eq!(hud_names(&mut app), vec!["old"]);
+
+    let replaced = app.world_mut().replace_scene_setup(
+        marker_component::<Hud>(),
+        |mut commands: Commands| {
+            commands.spawn((Hud, Name::new("new")));
+        },
+        true,
+    );
+
+    assert_eq!(replaced, Ok(()));
+    assert_eq!(hud_names(&mut app), vec!["new"]);
+    assert_eq!(app.world().resource::<Teardowns>().0, 1);
+}
+
+#[test]
+fn sliced_scene_setup_cannot_be_replaced_with_a_plain_one() {
+    let mut app = app();
+    app.add_sliced_scene(
+        marker_component::<Hud>(),
+        |In(_): In<SceneSlice>, mut commands: Commands| {
+            commands.spawn((Hud, Name::new("sliced")));
+            SceneBuildProgress::Complete
+        },
+        10,
+        Screen::Shop,
+    );
+    app.update();
+
+    let replaced = app.world_mut().replace_scene_setup(
+        marker_component::<Hud>(),
+        |mut commands: Commands| {
+            commands.spawn((Hud, Name::new("plain")));
+        },
+        false,
+    );
+    assert!(matches!(
+        replaced,
+        Err(OneShotSystemError::MismatchedTypes { .. })
+    ));
+    assert_eq!(errors(&app), vec![replaced.unwrap_err()]);
+
+    // The sliced setup system is still registered and builds the scene
+    enter_shop(&mut app);
+    app.update();
+    assert_eq!(hud_names(&mut app), vec!["sliced"]);
+}
+
+#[test]
+fn scenes_with_input_use_the_setup_system_registered_when_entered() {
+    let mut app = app();
+    app.add_scene_with_input(
+        marker_component::<Hud>(),
+        |In(count): In<u32>, mut commands: Commands| {
+            commands.spawn((Hud, Name::new(format!("old {count}"))));
+        },
+        Screen::Shop,
+    );
+    app.update();
+    app.world_mut().replace_marker_system::<Hud, u32, (), _>(
+        SCENE_SETUP_LABEL,
+        |In(count): In<u32>, mut commands: Commands| {
+            commands.spawn((Hud, Name::new(format!("new {count}"))));
+        },
+    );
+
+    app.world_mut()
+        .insert_resource(SceneInput::new(Screen::Shop, 3u32));
+    enter_shop(&mut app);
+
+    assert_eq!(hud_names(&mut app), vec!["new 3"]);
+    assert_eq!(errors(&app), vec![]);
+}
+
+#[test]
+fn sliced_scenes_use_the_setup_system_registered_when_entered() {
+    let mut app = app();
+    app.add_sliced_scene(
+        marker_component::<Hud>(),
+        |In(_): In<SceneSlice>, mut commands: Commands| {
+            commands.spawn((Hud, Name::new("old")));
+            SceneBuildProgress::Complete
+        },
+        10,
+        Screen::Shop,
+    );
+    app.update();
+    app.world_mut()
+        .replace_marker_system::<Hud, SceneSlice, SceneBuildProgress, _>(
+            SCENE_SETUP_LABEL,
+            |In(_): In<SceneSlice>, mut commands: Commands| {
+                commands.spawn((Hud, Name::new("new")));
+                SceneBuildProgress::Complete
+            },
+        );
+
+    enter_shop(&mut app);
+    app.update();
+
+    assert_eq!(hud_names(&mut app), vec!["new"]);
+    assert_eq!(errors(&app), vec![]);
+}
@@ -0,0 +1,84 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use ns_core::{
+    one_shot_system::{marker_component, OneShotSystemPlugin},
+    ui::scenes::*,
+};
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum GameState {
+    #[default]
+    Menu,
+    Loading,
+    Playing,
+}
+
+#[derive(Component, TypePath)]
+struct Level;
+
+#[derive(Resource, Default)]
+struct Received(Vec<u32>);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.init_state::<GameState>();
+    app.init_resource::<Received>();
+    app.add_scene_with_input(
+        marker_component::<Level>(),
+        |In(level): In<u32>, mut received: ResMut<Received>| received.0.push(level),
+        GameState::Playing,
+    );
+    app.update();
+    app
+}
+
+fn set_state(app: &mut App, state: GameState) {
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(state);
+    app.update();
+}
+
+#[test]
+fn input_is_handed_to_the_entered_scene_once() {
+    let mut app = app();
+    app.world_mut()
+        .commands()
+        .transition_with_input(GameState::Playing, 7u32);
+    app.world_mut().flush();
+    app.update();
+    assert!(!app.world().contains_resource::<SceneInput<u32>>());
+
+    set_state(&mut app, GameState::Menu);
+    set_state(&mut app, GameState::Playing);
+
+    assert_eq!(app.world().resource::<Received>().0, vec![7, 0]);
+}
+
+#[test]
+fn input_for_a_state_without_an_input_scene_does_not_leak() {
+    let mut app = app();
+    app.world_mut()
+        .commands()
+        .transition_with_input(GameState::Loading, 9u32);
+    app.world_mut().flush();
+    app.update();
+    assert!(!app.world().contains_resource::<SceneInput<u32>>());
+
+    set_state(&mut app, GameState::Playing);
+
+    assert_eq!(app.world().resource::<Received>().0, vec![0]);
+}
+
+#[test]
+fn input_is_only_used_by_the_state_it_was_supplied_for() {
+    let mut app = app();
+    app.world_mut()
+        .commands()
+        .set_scene_input(GameState::Loading, 9u32);
+    app.world_mut().flush();
+    set_state(&mut app, GameState::Playing);
+
+    assert_eq!(app.world().resource::<Received>().0, vec![0]);
+    assert!(!app.world().contains_resource::<SceneInput<u32>>());
+}
@@ -0,0 +1,115 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use bevy_eventlistener::event_listener::On;
+use bevy_simple_text_input::TextInputValue;
+use ns_core::{
+    one_shot_system::{marker_component, OneShotSystemPlugin},
+    ui::{
+        colors::CurrentColors,
+        scenes::*,
+        theme::Theme,
+        widgets::{
+            button::SelectedButton,
+            scroll::{ScrollEvent, Scrollable},
+            scroll_container::*,
+            tabbed_content::*,
+        },
+    },
+};
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum Game {
+    #[default]
+    Menu,
+    Shop,
+}
+
+#[derive(Component, TypePath)]
+struct Inventory;
+
+fn spawn_inventory(mut commands: Commands, theme: Res<Theme>) {
+    let (root, _) = tabbed_content(
+        Inventory,
+        TabbedContentSettings {
+            tabs: vec!["Weapons".into(), "Armor".into(), "Potions".into()],
+            open_tab: 0,
+        },
+        &theme,
+        &mut commands,
+    );
+    let scroll = scroll_container(
+        None::<Inventory>,
+        ScrollContainerSettings {
+            style: Style::default(),
+        },
+        &mut commands,
+    );
+    let search = commands.spawn(TextInputValue(String::new())).id();
+    commands.entity(root).push_children(&[scroll, search]);
+}
+
+fn set_state(app: &mut App, state: Game) {
+    app.world_mut().resource_mut::<NextState<Game>>().set(state);
+    app.update();
+}
+
+#[test]
+fn remembered_scenes_restore_their_widget_state() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.init_state::<Game>();
+    app.init_resource::<CurrentColors>();
+    app.init_resource::<Theme>();
+    app.add_scene(marker_component::<Inventory>(), spawn_inventory, Game::Shop);
+    app.remember_scene_state(marker_component::<Inventory>());
+
+    set_state(&mut app, Game::Shop);
+    let root = app
+        .world_mut()
+        .query_filtered::<Entity, With<TabbedContent>>()
+        .single(app.world());
+    select_tab(app.world_mut(), root, 2);
+    let scroll = app
+        .world_mut()
+        .query_filtered::<Entity, With<On<ScrollEvent>>>()
+        .single(app.world());
+    app.world_mut()
+        .entity_mut(scroll)
+        .insert(Scrollable::new(-42.0));
+    app.world_mut()
+        .query::<&mut TextInputValue>()
+        .single_mut(app.world_mut())
+        .0 = "potion".into();
+
+    set_state(&mut app, Game::Menu);
+    assert_eq!(
+        app.world_mut()
+            .query::<&TabbedContent>()
+            .iter(app.world())
+            .count(),
+        0
+    );
+
+    set_state(&mut app, Game::Shop);
+    let buttons = app
+        .world_mut()
+        .query::<&TabbedContent>()
+        .single(app.world())
+        .buttons()
+        .to_vec();
+    assert!(app.world().get::<SelectedButton>(buttons[0]).is_none());
+    assert!(app.world().get::<SelectedButton>(buttons[2]).is_some());
+    assert_eq!(
+        app.world_mut()
+            .query::<&Scrollable>()
+            .single(app.world())
+            .position(),
+        -42.0
+    );
+    assert_eq!(
+        app.world_mut()
+            .query::<&TextInputValue>()
+            .single(app.world())
+            .0,
+        "potion"
+    );
+}
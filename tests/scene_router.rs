@@ -0,0 +1,86 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*, state::app::StatesPlugin};
+use ns_core::ui::scenes::*;
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum Screen {
+    #[default]
+    Title,
+    Settings,
+    Controls,
+    Game,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin));
+    app.init_state::<Screen>();
+    app.add_scene_router::<Screen>();
+    app
+}
+
+/// Navigates with the given function and returns the state that was entered
+fn navigate<T: 'static>(
+    app: &mut App,
+    navigate: fn(&mut SceneNavigator<Screen>) -> T,
+) -> (T, Screen) {
+    let result = app
+        .world_mut()
+        .run_system_once(move |mut navigator: SceneNavigator<Screen>| navigate(&mut navigator));
+    app.update();
+    (result, *app.world().resource::<State<Screen>>().get())
+}
+
+#[test]
+fn back_returns_through_the_history() {
+    let mut app = app();
+    navigate(&mut app, |navigator| navigator.navigate(Screen::Settings));
+    navigate(&mut app, |navigator| navigator.navigate(Screen::Controls));
+
+    assert_eq!(
+        navigate(&mut app, |navigator| navigator.back()),
+        (true, Screen::Settings)
+    );
+    assert_eq!(
+        navigate(&mut app, |navigator| navigator.back()),
+        (true, Screen::Title)
+    );
+    assert_eq!(
+        navigate(&mut app, |navigator| navigator.back()),
+        (false, Screen::Title)
+    );
+}
+
+#[test]
+fn navigating_twice_in_a_frame_records_both_steps() {
+    let mut app = app();
+    let (_, entered) = navigate(&mut app, |navigator| {
+        navigator.navigate(Screen::Settings);
+        navigator.navigate(Screen::Controls);
+    });
+    assert_eq!(entered, Screen::Controls);
+    assert_eq!(
+        app.world().resource::<SceneRouter<Screen>>().history(),
+        &[Screen::Title, Screen::Settings]
+    );
+
+    assert_eq!(
+        navigate(&mut app, |navigator| navigator.back()),
+        (true, Screen::Settings)
+    );
+    assert_eq!(
+        navigate(&mut app, |navigator| navigator.back()),
+        (true, Screen::Title)
+    );
+}
+
+#[test]
+fn reset_clears_the_history() {
+    let mut app = app();
+    navigate(&mut app, |navigator| navigator.navigate(Screen::Settings));
+
+    assert_eq!(
+        navigate(&mut app, |navigator| navigator.reset_to(Screen::Game)).1,
+        Screen::Game
+    );
+    assert!(!app.world().resource::<SceneRouter<Screen>>().can_go_back());
+}
@@ -0,0 +1,131 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use ns_core::{
+    one_shot_system::{marker_component, OneShotSystemPlugin},
+    ui::{layers::UiLayer, scenes::*},
+};
+
+#[derive(Component, TypePath)]
+struct Inventory;
+
+#[derive(Component, TypePath)]
+struct Pause;
+
+#[derive(Component, TypePath)]
+struct Hud;
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum Screen {
+    #[default]
+    Game,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.init_state::<Screen>();
+    app.add_scene(
+        marker_component::<Hud>(),
+        |mut commands: Commands| {
+            commands.spawn((NodeBundle::default(), Hud));
+        },
+        Screen::Game,
+    );
+    app.add_overlay_scene(marker_component::<Inventory>(), |mut commands: Commands| {
+        commands.spawn((NodeBundle::default(), Inventory));
+    });
+    app.add_overlay_scene(marker_component::<Pause>(), |mut commands: Commands| {
+        commands.spawn((NodeBundle::default(), Pause));
+    });
+    app
+}
+
+fn visibility<Marker: Component>(app: &mut App) -> Visibility {
+    *app.world_mut()
+        .query_filtered::<&Visibility, With<Marker>>()
+        .single(app.world())
+}
+
+#[test]
+fn hide_hides_the_state_scene_and_lower_overlays() {
+    let mut app = app();
+    app.update();
+    let unrelated = app.world_mut().spawn(NodeBundle::default()).id();
+    let debug = app
+        .world_mut()
+        .spawn((NodeBundle::default(), UiLayer::Debug))
+        .id();
+
+    app.world_mut()
+        .commands()
+        .push_scene::<Inventory>(LowerSceneBehavior::Keep);
+    app.world_mut()
+        .commands()
+        .push_scene::<Pause>(LowerSceneBehavior::Hide);
+    app.update();
+
+    assert_eq!(visibility::<Hud>(&mut app), Visibility::Hidden);
+    assert_eq!(visibility::<Inventory>(&mut app), Visibility::Hidden);
+    assert_eq!(visibility::<Pause>(&mut app), Visibility::Inherited);
+    for entity in [unrelated, debug] {
+        assert_eq!(
+            *app.world().get::<Visibility>(entity).unwrap(),
+            Visibility::Inherited
+        );
+    }
+
+    app.world_mut().commands().pop_scene();
+    app.update();
+
+    assert_eq!(visibility::<Hud>(&mut app), Visibility::Inherited);
+    assert_eq!(visibility::<Inventory>(&mut app), Visibility::Inherited);
+    assert_eq!(app.world().resource::<SceneStack>().len(), 1);
+}
+
+#[test]
+fn pushing_a_scene_that_is_already_on_the_stack_does_nothing() {
+    let mut app = app();
+    app.world_mut()
+        .commands()
+        .push_scene::<Inventory>(LowerSceneBehavior::Keep);
+    app.world_mut()
+        .commands()
+        .push_scene::<Pause>(LowerSceneBehavior::Keep);
+    app.world_mut()
+        .commands()
+        .push_scene::<Inventory>(LowerSceneBehavior::Keep);
+    app.update();
+
+    assert_eq!(app.world().resource::<SceneStack>().len(), 2);
+    assert_eq!(
+        app.world().resource::<SceneStack>().top(),
+        Some(Pause::type_path())
+    );
+
+    app.world_mut().commands().pop_scene();
+    app.update();
+
+    // The inventory is still spawned once
+    assert_eq!(visibility::<Inventory>(&mut app), Visibility::Inherited);
+}
+
+#[test]
+fn popping_despawns_the_overlay_and_its_blocker() {
+    let mut app = app();
+    app.world_mut()
+        .commands()
+        .push_scene::<Pause>(LowerSceneBehavior::BlockInput);
+    app.update();
+    assert_eq!(
+        app.world_mut().query::<&Node>().iter(app.world()).count(),
+        3
+    );
+
+    app.world_mut().commands().pop_scene();
+    app.update();
+
+    assert!(app.world().resource::<SceneStack>().is_empty());
+    assert_eq!(
+        app.world_mut().query::<&Node>().iter(app.world()).count(),
+        1
+    );
+}
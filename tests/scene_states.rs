@@ -0,0 +1,92 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use ns_core::{
+    one_shot_system::{marker_component, OneShotSystemPlugin},
+    ui::scenes::*,
+};
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum Game {
+    #[default]
+    Menu,
+    Playing,
+    Paused,
+    Shop,
+}
+
+#[derive(Component, TypePath)]
+struct Hud;
+
+#[derive(Resource, Default)]
+struct Runs {
+    setups: u32,
+    teardowns: u32,
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.init_state::<Game>();
+    app.init_resource::<Runs>();
+    app.add_scene_teardown(marker_component::<Hud>(), |mut runs: ResMut<Runs>| {
+        runs.teardowns += 1
+    });
+    app
+}
+
+fn spawn_hud(mut commands: Commands, mut runs: ResMut<Runs>) {
+    runs.setups += 1;
+    commands.spawn(Hud);
+}
+
+fn set_state(app: &mut App, state: Game) {
+    app.world_mut().resource_mut::<NextState<Game>>().set(state);
+    app.update();
+}
+
+fn huds(app: &mut App) -> usize {
+    app.world_mut().query::<&Hud>().iter(app.world()).count()
+}
+
+#[test]
+fn scenes_in_states_survive_moving_between_those_states() {
+    let mut app = app();
+    app.add_scene_in_states(
+        marker_component::<Hud>(),
+        spawn_hud,
+        [Game::Playing, Game::Paused, Game::Shop],
+    );
+    app.update();
+    assert_eq!(huds(&mut app), 0);
+
+    for (state, expected) in [
+        (Game::Playing, 1),
+        (Game::Paused, 1),
+        (Game::Shop, 1),
+        (Game::Menu, 0),
+        (Game::Paused, 1),
+    ] {
+        set_state(&mut app, state);
+        assert_eq!(huds(&mut app), expected, "in {state:?}");
+    }
+
+    let runs = app.world().resource::<Runs>();
+    assert_eq!((runs.setups, runs.teardowns), (2, 1));
+}
+
+#[test]
+fn scenes_when_follow_the_predicate() {
+    let mut app = app();
+    app.add_scene_when(marker_component::<Hud>(), spawn_hud, |state: &Game| {
+        *state != Game::Menu
+    });
+    app.update();
+
+    set_state(&mut app, Game::Playing);
+    set_state(&mut app, Game::Shop);
+    assert_eq!(huds(&mut app), 1);
+    set_state(&mut app, Game::Menu);
+    assert_eq!(huds(&mut app), 0);
+
+    let runs = app.world().resource::<Runs>();
+    assert_eq!((runs.setups, runs.teardowns), (1, 1));
+}
@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use ns_core::{
+    one_shot_system::{marker_component, OneShotSystemPlugin},
+    ui::scenes::*,
+};
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum Game {
+    #[default]
+    Menu,
+    Shop,
+}
+
+#[derive(Component, TypePath)]
+struct Shop;
+
+#[derive(Component, TypePath)]
+struct Outer;
+
+#[derive(Component, TypePath)]
+struct Inner;
+
+fn slide_out() -> SceneTransitions {
+    SceneTransitions {
+        exit: SceneTransition::Slide(SlideEdge::Left),
+        duration: Duration::ZERO,
+        ..default()
+    }
+}
+
+fn pop(app: &mut App, count: usize) {
+    for _ in 0..count {
+        app.world_mut().commands().pop_scene();
+    }
+    app.world_mut().flush();
+    app.update();
+    app.update();
+}
+
+#[test]
+fn nested_scene_roots_finishing_together_are_despawned() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.add_overlay_scene(marker_component::<Outer>(), |mut commands: Commands| {
+        commands
+            .spawn((NodeBundle::default(), Outer))
+            .with_children(|parent| {
+                parent.spawn(NodeBundle::default());
+            });
+    });
+    // The inner scene is parented to the outer one, so despawning the outer root also despawns the inner root
+    app.add_overlay_scene(
+        marker_component::<Inner>(),
+        |mut commands: Commands, outer: Query<Entity, (With<Outer>, Without<Parent>)>| {
+            let inner = commands.spawn((NodeBundle::default(), Inner)).id();
+            commands.entity(outer.single()).add_child(inner);
+        },
+    );
+    app.set_scene_transitions(marker_component::<Outer>(), slide_out());
+    app.set_scene_transitions(marker_component::<Inner>(), slide_out());
+
+    // Animate the outer scene out once on its own first, so its roots are animated before the inner ones from then on
+    app.world_mut()
+        .commands()
+        .push_scene::<Outer>(LowerSceneBehavior::Keep);
+    app.world_mut().flush();
+    app.update();
+    pop(&mut app, 1);
+
+    app.world_mut()
+        .commands()
+        .push_scene::<Outer>(LowerSceneBehavior::Keep);
+    app.world_mut().flush();
+    app.update();
+    app.world_mut()
+        .commands()
+        .push_scene::<Inner>(LowerSceneBehavior::Keep);
+    app.world_mut().flush();
+    app.update();
+    pop(&mut app, 2);
+
+    assert_eq!(
+        app.world_mut().query::<&Node>().iter(app.world()).count(),
+        0
+    );
+}
+
+fn set_state(app: &mut App, state: Game) {
+    app.world_mut().resource_mut::<NextState<Game>>().set(state);
+    app.update();
+}
+
+fn background_alpha(app: &mut App) -> f32 {
+    app.world_mut()
+        .query_filtered::<&BackgroundColor, With<Shop>>()
+        .single(app.world())
+        .0
+        .alpha()
+}
+
+fn nodes(app: &mut App) -> usize {
+    app.world_mut().query::<&Node>().iter(app.world()).count()
+}
+
+#[test]
+fn scenes_animate_in_and_are_despawned_once_animated_out() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        20,
+    )));
+    app.init_state::<Game>();
+    app.add_scene(
+        marker_component::<Shop>(),
+        |mut commands: Commands| {
+            let background = NodeBundle {
+                background_color: Color::WHITE.into(),
+                ..default()
+            };
+            commands.spawn((background, Shop)).with_children(|parent| {
+                parent.spawn(TextBundle::from_section("Shop", TextStyle::default()));
+            });
+        },
+        Game::Shop,
+    );
+    app.set_scene_transitions(
+        marker_component::<Shop>(),
+        SceneTransitions {
+            enter: SceneTransition::Fade,
+            exit: SceneTransition::Slide(SlideEdge::Left),
+            duration: Duration::from_millis(50),
+        },
+    );
+    // The first update doesn't advance time
+    app.update();
+
+    set_state(&mut app, Game::Shop);
+    assert!(background_alpha(&mut app) < 0.5);
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(background_alpha(&mut app), 1.0);
+    assert_eq!(
+        app.world_mut()
+            .query::<&SceneAnimation>()
+            .iter(app.world())
+            .count(),
+        0
+    );
+
+    // The scene is unmarked straight away, but kept on screen until it has slid out
+    set_state(&mut app, Game::Menu);
+    assert_eq!(nodes(&mut app), 2);
+    assert_eq!(
+        app.world_mut().query::<&Shop>().iter(app.world()).count(),
+        0
+    );
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(nodes(&mut app), 0);
+}
@@ -0,0 +1,77 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use ns_core::{
+    one_shot_system::{marker_component, OneShotSystemPlugin},
+    ui::scenes::*,
+};
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum Game {
+    #[default]
+    Menu,
+    Shop,
+}
+
+#[derive(Component, TypePath)]
+struct Inventory;
+
+/// Spawns ten items, as many per frame as the slice allows
+fn spawn_items(In(slice): In<SceneSlice>, mut commands: Commands) -> SceneBuildProgress {
+    for item in slice.range() {
+        if item >= 10 {
+            return SceneBuildProgress::Complete;
+        }
+        commands.spawn((NodeBundle::default(), Inventory));
+    }
+    SceneBuildProgress::Building
+}
+
+fn items(app: &mut App) -> Vec<Visibility> {
+    app.world_mut()
+        .query_filtered::<&Visibility, With<Inventory>>()
+        .iter(app.world())
+        .copied()
+        .collect()
+}
+
+#[test]
+fn sliced_scenes_are_built_over_several_frames_and_hidden_until_ready() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.init_state::<Game>();
+    app.add_sliced_scene(marker_component::<Inventory>(), spawn_items, 4, Game::Shop);
+    app.world_mut()
+        .resource_mut::<NextState<Game>>()
+        .set(Game::Shop);
+
+    app.update();
+    assert_eq!(items(&mut app), vec![Visibility::Hidden; 4]);
+
+    app.update();
+    app.update();
+    assert_eq!(items(&mut app), vec![Visibility::Inherited; 10]);
+    let events = app.world().resource::<Events<SceneReady>>();
+    let ready: Vec<_> = events.get_reader().read(events).copied().collect();
+    assert_eq!(ready.len(), 1);
+    assert!(ready[0].is::<Inventory>());
+}
+
+#[test]
+fn leaving_stops_the_build() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.init_state::<Game>();
+    app.add_sliced_scene(marker_component::<Inventory>(), spawn_items, 4, Game::Shop);
+    app.world_mut()
+        .resource_mut::<NextState<Game>>()
+        .set(Game::Shop);
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<NextState<Game>>()
+        .set(Game::Menu);
+    app.update();
+    app.update();
+
+    assert!(items(&mut app).is_empty());
+    assert!(app.world().resource::<Events<SceneReady>>().is_empty());
+}
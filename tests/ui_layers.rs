@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use ns_core::ui::layers::*;
+
+fn global_z(app: &App, entity: Entity) -> i32 {
+    match app.world().get::<ZIndex>(entity) {
+        Some(ZIndex::Global(z)) => *z,
+        other => panic!("expected a global z index, found {other:?}"),
+    }
+}
+
+#[test]
+fn layers_are_drawn_in_the_configured_order() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, UiLayersPlugin));
+    let tooltip = app
+        .world_mut()
+        .spawn((NodeBundle::default(), UiLayer::Tooltip))
+        .id();
+    let modal = app
+        .world_mut()
+        .spawn((NodeBundle::default(), UiLayer::Modal))
+        .id();
+    app.update();
+    assert!(global_z(&app, tooltip) > global_z(&app, modal));
+
+    app.insert_resource(UiLayers::new([
+        UiLayer::Hud,
+        UiLayer::Tooltip,
+        UiLayer::Modal,
+    ]));
+    app.update();
+    assert!(global_z(&app, tooltip) < global_z(&app, modal));
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_layer_constants_match_the_default_layers() {
+    let layers = UiLayers::default();
+
+    assert_eq!(ns_core::ui::UI_SCREEN_LAYER, layers.global_z(UiLayer::Menu));
+    assert_eq!(ns_core::ui::UI_MODAL_LAYER, layers.global_z(UiLayer::Modal));
+}
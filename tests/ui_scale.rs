@@ -0,0 +1,121 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use ns_core::ui::{scale, ScaleUi, ScaleUiMode, UserUiScale};
+
+fn window(width: f32, height: f32) -> Window {
+    let mut window = Window::default();
+    window.resolution.set(width, height);
+    window
+}
+
+fn app(window: Window) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.init_resource::<UiScale>();
+    app.add_systems(Update, scale);
+    app.world_mut().spawn((window, PrimaryWindow));
+    app
+}
+
+fn ui_scale(app: &App) -> f32 {
+    app.world().resource::<UiScale>().0
+}
+
+#[test]
+fn modes_compare_the_window_against_the_reference_resolution() {
+    let settings = ScaleUi::default();
+    let with_mode = |mode| ScaleUi { mode, ..settings };
+
+    assert_eq!(settings.scale_for(&window(3840.0, 1080.0)), 1.0);
+    assert_eq!(
+        with_mode(ScaleUiMode::Fill).scale_for(&window(3840.0, 1080.0)),
+        2.0
+    );
+    assert_eq!(
+        with_mode(ScaleUiMode::MatchWidth).scale_for(&window(960.0, 2160.0)),
+        0.5
+    );
+    assert_eq!(
+        with_mode(ScaleUiMode::MatchHeight).scale_for(&window(960.0, 2160.0)),
+        2.0
+    );
+    assert_eq!(
+        with_mode(ScaleUiMode::IntegerFit).scale_for(&window(5000.0, 3000.0)),
+        2.0
+    );
+    assert_eq!(
+        with_mode(ScaleUiMode::IntegerFit).scale_for(&window(1000.0, 500.0)),
+        1.0
+    );
+}
+
+#[test]
+fn clamps_limit_the_scale() {
+    let settings = ScaleUi {
+        min: Some(0.75),
+        max: Some(1.5),
+        ..default()
+    };
+
+    assert_eq!(settings.scale_for(&window(960.0, 540.0)), 0.75);
+    assert_eq!(settings.scale_for(&window(7680.0, 4320.0)), 1.5);
+}
+
+#[test]
+fn dpi_aware_fits_the_reference_resolution_at_the_display_scale_factor() {
+    let fit = ScaleUi::default();
+    let dpi_aware = ScaleUi {
+        mode: ScaleUiMode::DpiAware,
+        ..fit
+    };
+    let mut window = window(3840.0, 2160.0);
+    window.resolution.set_scale_factor(2.0);
+    assert_eq!(dpi_aware.scale_for(&window), 1.0);
+    assert_eq!(dpi_aware.scale_for(&window), fit.scale_for(&window));
+
+    // The override doubles the logical size of the window, which fit follows but the display scaling doesn't
+    window.resolution.set_scale_factor_override(Some(1.0));
+    assert_eq!(fit.scale_for(&window), 2.0);
+    assert_eq!(dpi_aware.scale_for(&window), 4.0);
+}
+
+#[test]
+fn user_scale_multiplies_the_automatic_scale() {
+    let mut app = app(window(3840.0, 2160.0));
+    app.insert_resource(ScaleUi::default());
+    app.update();
+    assert_eq!(ui_scale(&app), 2.0);
+
+    app.insert_resource(UserUiScale(1.5));
+    app.update();
+    assert_eq!(ui_scale(&app), 3.0);
+
+    app.world_mut().resource_mut::<UserUiScale>().0 = 0.5;
+    app.update();
+    assert_eq!(ui_scale(&app), 1.0);
+}
+
+#[test]
+fn removing_the_scale_resources_resets_the_ui_scale() {
+    let mut app = app(window(3840.0, 2160.0));
+    app.insert_resource(ScaleUi::default());
+    app.insert_resource(UserUiScale(1.5));
+    app.update();
+    assert_eq!(ui_scale(&app), 3.0);
+
+    app.world_mut().remove_resource::<UserUiScale>();
+    app.update();
+    assert_eq!(ui_scale(&app), 2.0);
+
+    app.world_mut().remove_resource::<ScaleUi>();
+    app.update();
+    assert_eq!(ui_scale(&app), 1.0);
+}
+
+#[test]
+fn ui_scale_is_left_alone_without_the_scale_resources() {
+    let mut app = app(window(3840.0, 2160.0));
+    app.insert_resource(UiScale(1.25));
+    app.update();
+
+    assert_eq!(ui_scale(&app), 1.25);
+}
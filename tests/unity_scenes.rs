@@ -0,0 +1,123 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use ns_core::{experimental::unity_scenes::*, one_shot_system::*};
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+enum GameState {
+    #[default]
+    Menu,
+    Playing,
+}
+
+#[derive(Component, TypePath)]
+struct Enemy;
+
+#[derive(Resource, Default)]
+struct Ticks(u32);
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, OneShotSystemPlugin));
+    app.init_state::<GameState>();
+    app.add_plugins(UnityScenePlugin {
+        state: GameState::Playing,
+    });
+    app.update();
+    app
+}
+
+fn set_state(app: &mut App, state: GameState) {
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(state);
+    app.update();
+}
+
+fn names(app: &mut App) -> Vec<String> {
+    let mut names: Vec<String> = app
+        .world_mut()
+        .query::<&Name>()
+        .iter(app.world())
+        .map(|name| name.to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn entities_spawned_in_the_state_are_despawned_on_exit() {
+    let mut app = app();
+    app.world_mut().spawn(Name::new("before"));
+    app.add_systems(OnEnter(GameState::Playing), |mut commands: Commands| {
+        commands.spawn(Name::new("on enter"));
+    });
+
+    set_state(&mut app, GameState::Playing);
+    app.world_mut().spawn(Name::new("during"));
+    app.update();
+    app.world_mut().spawn(Name::new("just before exit"));
+    set_state(&mut app, GameState::Menu);
+
+    assert_eq!(names(&mut app), vec!["before"]);
+}
+
+#[test]
+fn descendants_of_persistent_entities_survive() {
+    let mut app = app();
+    set_state(&mut app, GameState::Playing);
+
+    app.world_mut()
+        .spawn((Name::new("persistent"), PersistentEntity))
+        .with_children(|parent| {
+            parent.spawn(Name::new("child")).with_children(|parent| {
+                parent.spawn(Name::new("grandchild"));
+            });
+        });
+    app.update();
+    set_state(&mut app, GameState::Menu);
+
+    assert_eq!(names(&mut app), vec!["child", "grandchild", "persistent"]);
+}
+
+#[test]
+fn registered_systems_are_not_managed() {
+    let mut app = app();
+    set_state(&mut app, GameState::Playing);
+
+    app.world_mut().replace_marker_system::<Enemy, (), (), _>(
+        DEFAULT_LABEL,
+        |mut commands: Commands| {
+            commands.spawn(Name::new("spawned by system"));
+        },
+    );
+    let direct = app.world_mut().register_system(|| {});
+    app.world_mut()
+        .entity_mut(direct.entity())
+        .insert(PersistentEntity);
+    app.update();
+    set_state(&mut app, GameState::Menu);
+
+    assert!(app.world_mut().run_marker_system::<Enemy>().is_ok());
+    assert!(app.world_mut().run_system(direct).is_ok());
+}
+
+#[test]
+fn scheduled_systems_keep_running_after_the_state_is_left() {
+    let mut app = app();
+    app.init_resource::<Ticks>();
+    app.register_one_shot_system(marker_component::<Enemy>(), |mut ticks: ResMut<Ticks>| {
+        ticks.0 += 1
+    });
+    set_state(&mut app, GameState::Playing);
+
+    let handle = app
+        .world_mut()
+        .schedule_labeled_marker_system::<Enemy>(DEFAULT_LABEL, OneShotSchedule::AfterFrames(3));
+    app.update();
+    set_state(&mut app, GameState::Menu);
+
+    assert!(handle.is_scheduled(app.world()));
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<Ticks>().0, 1);
+}
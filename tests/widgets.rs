@@ -0,0 +1,252 @@
+use bevy::{ecs::system::RunSystemOnce, input::InputPlugin, prelude::*};
+use ns_core::ui::{
+    colors::*,
+    layers::{UiLayer, UiLayers},
+    theme::{Theme, ThemePlugin},
+    widgets::{button::*, modal::*, tabbed_content::*, WidgetsPlugin},
+};
+
+#[derive(Component)]
+struct Menu;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        InputPlugin,
+        GameColorsPlugin {
+            custom_colors: None,
+        },
+        ThemePlugin,
+        WidgetsPlugin,
+    ));
+    app
+}
+
+fn spawn_button(app: &mut App) -> Entity {
+    app.world_mut()
+        .run_system_once(|mut commands: Commands, theme: Res<Theme>| {
+            basic_button(
+                Menu,
+                BasicButtonStyle::<()> {
+                    text: "Play".into(),
+                    ..default()
+                },
+                &mut commands,
+                &theme,
+            )
+        })
+}
+
+fn background(app: &App, entity: Entity) -> Color {
+    app.world().get::<BackgroundColor>(entity).unwrap().0
+}
+
+#[test]
+fn widgets_are_sized_by_the_theme() {
+    let mut app = app();
+    let mut theme = Theme::default();
+    theme.font_sizes.button = 12.0;
+    theme.spacing.medium = 3.0;
+    theme.corner_radii.small = 6.0;
+    app.insert_resource(theme);
+
+    let button = spawn_button(&mut app);
+    app.update();
+
+    let text = app.world().get::<Children>(button).unwrap()[0];
+    assert_eq!(
+        app.world().get::<Text>(text).unwrap().sections[0]
+            .style
+            .font_size,
+        12.0
+    );
+    assert_eq!(
+        app.world().get::<Style>(button).unwrap().margin,
+        UiRect::all(Val::Px(3.0))
+    );
+    assert_eq!(
+        *app.world().get::<BorderRadius>(button).unwrap(),
+        BorderRadius::all(Val::Px(6.0))
+    );
+}
+
+#[test]
+fn default_theme_keeps_square_corners() {
+    let mut app = app();
+
+    let button = spawn_button(&mut app);
+    app.update();
+
+    assert_eq!(
+        *app.world().get::<BorderRadius>(button).unwrap(),
+        BorderRadius::all(Val::Px(0.0))
+    );
+}
+
+#[test]
+fn modals_start_on_the_modal_layer() {
+    let mut app = app();
+    let content = app
+        .world_mut()
+        .run_system_once(|mut commands: Commands, theme: Res<Theme>| {
+            modal_panel(
+                Menu,
+                ModalStyle {
+                    can_close: false,
+                    close_button_bundle: None::<()>,
+                    modal_size: None,
+                    outline: false,
+                },
+                &theme,
+                &mut commands,
+            )
+        });
+    app.update();
+
+    // No `UiLayersPlugin` is added, the modal has to be drawn over screens on its own
+    let body = app.world().get::<Parent>(content).unwrap().get();
+    let root = app.world().get::<Parent>(body).unwrap().get();
+    assert_eq!(
+        *app.world().get::<ZIndex>(root).unwrap(),
+        UiLayers::default().z_index(UiLayer::Modal, 0)
+    );
+}
+
+#[test]
+fn spawned_widgets_follow_the_current_colors() {
+    let mut app = app();
+    let (content, root) =
+        app.world_mut()
+            .run_system_once(|mut commands: Commands, theme: Res<Theme>| {
+                let content = modal_panel(
+                    Menu,
+                    ModalStyle {
+                        can_close: true,
+                        close_button_bundle: None::<()>,
+                        modal_size: None,
+                        outline: true,
+                    },
+                    &theme,
+                    &mut commands,
+                );
+                let (root, _) = tabbed_content(
+                    Menu,
+                    TabbedContentSettings {
+                        tabs: vec!["Video".into(), "Audio".into()],
+                        open_tab: 0,
+                    },
+                    &theme,
+                    &mut commands,
+                );
+                (content, root)
+            });
+    app.update();
+
+    let palette = GameColorPalette {
+        background: Color::WHITE,
+        background_dark: Color::BLACK,
+        accent: Color::srgb(1.0, 0.0, 0.0),
+        ..GameColorPalette::dark()
+    };
+    app.insert_resource(CurrentColors(palette));
+    app.update();
+
+    let body = app.world().get::<Parent>(content).unwrap().get();
+    assert_eq!(background(&app, body), Color::WHITE);
+    assert_eq!(
+        app.world().get::<BorderColor>(body).unwrap().0,
+        Color::BLACK
+    );
+    assert_eq!(background(&app, root), Color::WHITE);
+    let buttons = app
+        .world()
+        .get::<TabbedContent>(root)
+        .unwrap()
+        .buttons()
+        .to_vec();
+    assert_eq!(background(&app, buttons[1]), Color::BLACK);
+    assert_eq!(
+        app.world().get::<Outline>(buttons[0]).unwrap().color,
+        Color::srgb(1.0, 0.0, 0.0)
+    );
+}
+
+#[test]
+fn buttons_use_the_color_variants_of_their_state() {
+    let mut app = app();
+    let button = spawn_button(&mut app);
+    app.update();
+    let colors = *app.world().resource::<CurrentColors>();
+    let disabled = colors.color_variant(PaletteRole::BackgroundDark, ColorVariant::Disabled);
+    assert_eq!(background(&app, button), colors.background_dark());
+
+    app.world_mut().entity_mut(button).insert(DisabledButton);
+    app.update();
+    assert_eq!(background(&app, button), disabled);
+    let text = app.world().get::<Children>(button).unwrap()[0];
+    assert_eq!(
+        app.world().get::<Text>(text).unwrap().sections[0]
+            .style
+            .color,
+        colors.disabled()
+    );
+
+    // Disabled buttons don't react to the pointer
+    *app.world_mut().get_mut::<Interaction>(button).unwrap() = Interaction::Hovered;
+    app.update();
+    assert_eq!(background(&app, button), disabled);
+
+    app.world_mut()
+        .entity_mut(button)
+        .remove::<DisabledButton>();
+    app.update();
+    assert_eq!(
+        background(&app, button),
+        colors.color_variant(PaletteRole::BackgroundDark, ColorVariant::Hovered)
+    );
+}
+
+#[test]
+fn theme_colors_stay_in_sync_with_the_current_colors() {
+    let mut app = app();
+
+    app.insert_resource(CurrentColors(GameColorPalette::light()));
+    app.update();
+    assert_eq!(
+        app.world().resource::<Theme>().colors,
+        CurrentColors(GameColorPalette::light())
+    );
+
+    app.world_mut().resource_mut::<Theme>().colors =
+        CurrentColors(GameColorPalette::high_contrast());
+    app.update();
+    assert_eq!(
+        *app.world().resource::<CurrentColors>(),
+        CurrentColors(GameColorPalette::high_contrast())
+    );
+}
+
+#[test]
+fn widgets_work_without_the_theme_plugin() {
+    let custom_colors = CurrentColors(GameColorPalette::light());
+    let mut app = App::new();
+    // The theme is created before the custom colors are inserted, the custom colors still win
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        InputPlugin,
+        WidgetsPlugin,
+        GameColorsPlugin {
+            custom_colors: Some(custom_colors),
+        },
+    ));
+
+    let button = spawn_button(&mut app);
+    app.update();
+
+    assert_eq!(*app.world().resource::<CurrentColors>(), custom_colors);
+    assert_eq!(app.world().resource::<Theme>().colors, custom_colors);
+    assert!(app.world().get::<BasicButton>(button).is_some());
+}